@@ -0,0 +1,157 @@
+// Command dispatch without a webview: cargo bench --bench dispatch
+//
+// Runs the app's own command handler (`register`) through Tauri's mock runtime
+// (`tauri::test::mock_builder` + `get_ipc_response`), so each call goes
+// through the real IPC routing, argument deserialization and response
+// serialization but never touches a webview. Comparing the result with
+// BENCHMARK=ipc / BENCHMARK=payload separates the Rust-side dispatch cost from
+// the webview's, and it runs on machines with no display.
+//
+// Same commands and payloads as the live drivers: `update_title` and `echo`
+// with the shared `DATA` payload from ipc.bench.ts, `bench_config`, and the
+// `payload_*` commands at each BENCHMARK_PAYLOAD_SIZES. BENCHMARK_ITERATIONS,
+// BENCHMARK_WARMUP and BENCHMARK_PAYLOAD_ITERATIONS apply as in the app.
+// Prints one JSON line shaped like the app's "ipc" section, with a
+// `payload_bytes` per entry.
+
+use std::time::Instant;
+
+use serde_json::{json, Value};
+use tauri::ipc::{CallbackFn, InvokeBody};
+use tauri::test::{get_ipc_response, mock_builder, mock_context, noop_assets, INVOKE_KEY};
+use tauri::webview::InvokeRequest;
+use tauri::{WebviewWindow, WebviewWindowBuilder};
+
+use tauri_hello_world_lib::histogram::Histogram;
+use tauri_hello_world_lib::mode::{BenchConfig, Mode};
+use tauri_hello_world_lib::report::Report;
+use tauri_hello_world_lib::stats::Summary;
+
+type MockWindow = WebviewWindow<tauri::test::MockRuntime>;
+
+fn request(cmd: &str, body: InvokeBody) -> InvokeRequest {
+    InvokeRequest {
+        cmd: cmd.into(),
+        callback: CallbackFn(0),
+        error: CallbackFn(1),
+        url: "http://tauri.localhost".parse().unwrap(),
+        body,
+        headers: Default::default(),
+        invoke_key: INVOKE_KEY.to_string(),
+    }
+}
+
+/// Call `cmd` `warmup + iterations` times and summarize the timed calls.
+fn measure(
+    window: &MockWindow,
+    cmd: &str,
+    body: InvokeBody,
+    payload_bytes: usize,
+    warmup: u64,
+    iterations: u64,
+) -> Value {
+    if let Err(error) = get_ipc_response(window, request(cmd, body.clone())) {
+        panic!("{cmd} failed: {error}");
+    }
+    let mut samples = Vec::with_capacity(iterations as usize);
+    for i in 0..warmup + iterations {
+        // Build the request outside the timed region; cloning a 10 MB body
+        // is not dispatch cost.
+        let request = request(cmd, body.clone());
+        let start = Instant::now();
+        let response = get_ipc_response(window, request);
+        let elapsed = start.elapsed();
+        drop(response);
+        if i >= warmup {
+            samples.push(elapsed.as_secs_f64() * 1e3);
+        }
+    }
+    json!({
+        "command": cmd,
+        "payload_bytes": payload_bytes,
+        "latency_ms": Summary::from_samples(&samples),
+        "latency_histogram": Histogram::from_samples(&samples).report(),
+    })
+}
+
+fn main() {
+    let config = BenchConfig::from_env();
+    let app = tauri_hello_world_lib::register(mock_builder(), Report::with_json(Mode::Ipc, false))
+        .build(mock_context(noop_assets()))
+        .expect("failed to build the mock app");
+    let window = WebviewWindowBuilder::new(&app, "main", Default::default())
+        .build()
+        .expect("failed to create the mock window");
+
+    let data = json!({
+        "title": "Hello World",
+        "timestamp": 1708200000000u64,
+        "metadata": { "source": "renderer", "priority": 1 },
+    });
+    let data_bytes = data.to_string().len();
+    let (warmup, iterations) = (config.warmup, config.iterations);
+    let mut results = vec![
+        measure(
+            &window,
+            "update_title",
+            InvokeBody::Json(json!({ "data": data })),
+            data_bytes,
+            warmup,
+            iterations,
+        ),
+        measure(
+            &window,
+            "echo",
+            InvokeBody::Json(json!({ "value": data })),
+            data_bytes,
+            warmup,
+            iterations,
+        ),
+        measure(
+            &window,
+            "bench_config",
+            InvokeBody::Json(json!({})),
+            0,
+            warmup,
+            iterations,
+        ),
+    ];
+
+    let payload_warmup = config.payload_iterations.min(warmup);
+    for &bytes in &config.payload_sizes {
+        let len = bytes as usize;
+        let bodies = [
+            (
+                "payload_string",
+                InvokeBody::Json(json!({ "data": "x".repeat(len) })),
+            ),
+            (
+                "payload_bytes",
+                InvokeBody::Json(json!({ "data": vec![b'x'; len] })),
+            ),
+            ("payload_raw", InvokeBody::Raw(vec![b'x'; len])),
+        ];
+        for (cmd, body) in bodies {
+            results.push(measure(
+                &window,
+                cmd,
+                body,
+                len,
+                payload_warmup,
+                config.payload_iterations,
+            ));
+        }
+    }
+
+    println!(
+        "{}",
+        json!({
+            "scenario": "dispatch",
+            "runtime": "mock",
+            "iterations": iterations,
+            "warmup": warmup,
+            "payload_iterations": config.payload_iterations,
+            "commands": results,
+        })
+    );
+}
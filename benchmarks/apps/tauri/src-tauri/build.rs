@@ -1,3 +1,186 @@
+// Tauri codegen, plus the synthetic frontend used by BENCHMARK=synthetic.
+//
+// BENCHMARK_SYNTHETIC_ASSETS=N at build time writes ../src/synthetic/: an
+// index.html that references N generated files, cycling through JS, CSS and
+// SVG images, each padded to BENCHMARK_SYNTHETIC_ASSET_BYTES (default 1024).
+// The directory sits inside frontendDist, so the files are embedded exactly
+// like the real frontend. Without the variable the page references nothing.
+// The chosen values are passed on to the app so it can report them.
+//
+// It also writes ../src/fixtures/<bytes>.bin for each size in
+// BENCHMARK_ASSET_FIXTURES (comma separated; default the BENCHMARK=assets
+// sizes, 1 KB to 1 MB; empty for none), so the assets driver can fetch
+// embedded files of the same sizes as the custom schemes serve. The bodies
+// are repetitive enough that embedding them barely grows the binary. The
+// sizes are passed on as ASSET_FIXTURES.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const DIR: &str = "../src/synthetic";
+const FIXTURES: &str = "../src/fixtures";
+const DEFAULT_FIXTURES: &str = "1024,10240,102400,1048576";
+
 fn main() {
+    println!("cargo:rerun-if-env-changed=BENCHMARK_SYNTHETIC_ASSETS");
+    println!("cargo:rerun-if-env-changed=BENCHMARK_SYNTHETIC_ASSET_BYTES");
+    println!("cargo:rerun-if-changed={DIR}");
+
+    let count = env_usize("BENCHMARK_SYNTHETIC_ASSETS", 0);
+    let bytes = env_usize("BENCHMARK_SYNTHETIC_ASSET_BYTES", 1024);
+    synthetic_frontend(Path::new(DIR), count, bytes).expect("failed to write synthetic frontend");
+    println!("cargo:rustc-env=SYNTHETIC_ASSETS={count}");
+    println!("cargo:rustc-env=SYNTHETIC_ASSET_BYTES={bytes}");
+
+    println!("cargo:rerun-if-env-changed=BENCHMARK_ASSET_FIXTURES");
+    println!("cargo:rerun-if-changed={FIXTURES}");
+    let sizes: Vec<usize> = std::env::var("BENCHMARK_ASSET_FIXTURES")
+        .unwrap_or_else(|_| DEFAULT_FIXTURES.to_owned())
+        .split(',')
+        .filter_map(|n| n.trim().parse().ok())
+        .collect();
+    asset_fixtures(Path::new(FIXTURES), &sizes).expect("failed to write asset fixtures");
+    let sizes: Vec<String> = sizes.iter().map(usize::to_string).collect();
+    println!("cargo:rustc-env=ASSET_FIXTURES={}", sizes.join(","));
+
     tauri_build::build()
 }
+
+fn env_usize(name: &str, default: usize) -> usize {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(default)
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Js,
+    Css,
+    Svg,
+}
+
+impl Kind {
+    fn of(index: usize) -> Self {
+        [Kind::Js, Kind::Css, Kind::Svg][index % 3]
+    }
+
+    fn dir(self) -> &'static str {
+        match self {
+            Kind::Js => "js",
+            Kind::Css => "css",
+            Kind::Svg => "img",
+        }
+    }
+
+    fn path(self, index: usize) -> String {
+        let ext = match self {
+            Kind::Js => "js",
+            Kind::Css => "css",
+            Kind::Svg => "svg",
+        };
+        format!("{}/{index}.{ext}", self.dir())
+    }
+
+    /// File body, padded with a comment to roughly `bytes`.
+    fn body(self, index: usize, bytes: usize) -> String {
+        let (head, open, close, tail) = match self {
+            Kind::Js => (
+                format!("window.__synthetic = (window.__synthetic || 0) + 1 // {index}\n"),
+                "/*",
+                "*/\n",
+                "",
+            ),
+            Kind::Css => (
+                format!(".s{index} {{ color: #{:06x} }}\n", index & 0xFF_FFFF),
+                "/*",
+                "*/\n",
+                "",
+            ),
+            Kind::Svg => (
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"1\" height=\"1\">".to_owned(),
+                "<!--",
+                "-->",
+                "</svg>\n",
+            ),
+        };
+        let used = head.len() + open.len() + close.len() + tail.len();
+        let padding = "x".repeat(bytes.saturating_sub(used));
+        format!("{head}{open}{padding}{close}{tail}")
+    }
+
+    /// How the page references the file.
+    fn tag(self, index: usize) -> String {
+        let path = self.path(index);
+        match self {
+            Kind::Js => format!("  <script src=\"{path}\"></script>\n"),
+            Kind::Css => format!("  <link rel=\"stylesheet\" href=\"{path}\">\n"),
+            Kind::Svg => format!("  <img src=\"{path}\" width=\"1\" height=\"1\" alt=\"\">\n"),
+        }
+    }
+}
+
+fn synthetic_frontend(dir: &Path, count: usize, bytes: usize) -> io::Result<()> {
+    let mut head = String::new();
+    let mut body = String::new();
+    let mut wanted = HashSet::new();
+
+    for index in 0..count {
+        let kind = Kind::of(index);
+        let path = dir.join(kind.path(index));
+        fs::create_dir_all(path.parent().unwrap())?;
+        write_if_changed(&path, &kind.body(index, bytes))?;
+        wanted.insert(path);
+        match kind {
+            Kind::Css => head.push_str(&kind.tag(index)),
+            Kind::Js | Kind::Svg => body.push_str(&kind.tag(index)),
+        }
+    }
+
+    let page = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n  <meta charset=\"UTF-8\">\n  <title>Hello World</title>\n{head}</head>\n<body>\n  <h1>Hello World</h1>\n{body}</body>\n</html>\n"
+    );
+    fs::create_dir_all(dir)?;
+    write_if_changed(&dir.join("index.html"), &page)?;
+
+    // Drop files left over from a build with more assets.
+    for kind in [Kind::Js, Kind::Css, Kind::Svg] {
+        let Ok(entries) = fs::read_dir(dir.join(kind.dir())) else {
+            continue;
+        };
+        for entry in entries {
+            let path = entry?.path();
+            if !wanted.contains(&path) {
+                fs::remove_file(path)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn asset_fixtures(dir: &Path, sizes: &[usize]) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let mut wanted = HashSet::new();
+    for &bytes in sizes {
+        let path = dir.join(format!("{bytes}.bin"));
+        write_if_changed(&path, &"x".repeat(bytes))?;
+        wanted.insert(path);
+    }
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !wanted.contains(&path) {
+            fs::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Leave unchanged files alone so their mtimes don't trigger rebuilds.
+fn write_if_changed(path: &Path, contents: &str) -> io::Result<()> {
+    if fs::read_to_string(path).is_ok_and(|old| old == contents) {
+        return Ok(());
+    }
+    fs::write(path, contents)
+}
@@ -0,0 +1,55 @@
+/**
+ * First-paint probe (BENCHMARK=1 BENCHMARK_READY=paint)
+ *
+ * Injected as an initialization script so it runs before the page parses.
+ * Once the <h1> is in the DOM it waits two animation frames — the first
+ * callback runs before the frame containing the heading is painted, the
+ * second after it — then reports paint, DOMContentLoaded and load times
+ * (all performance.now(), i.e. relative to performance.timeOrigin) to the
+ * `paint_report` command.
+ */
+(() => {
+  if (window.top !== window) return
+
+  const marks = { domContentLoaded: null, load: null }
+  document.addEventListener('DOMContentLoaded', () => {
+    marks.domContentLoaded = performance.now()
+  })
+  const loaded = new Promise((resolve) => {
+    window.addEventListener('load', () => {
+      marks.load = performance.now()
+      resolve()
+    })
+  })
+
+  const heading = new Promise((resolve) => {
+    const found = () => document.querySelector('h1') !== null
+    if (found()) return resolve()
+    const observer = new MutationObserver(() => {
+      if (found()) {
+        observer.disconnect()
+        resolve()
+      }
+    })
+    observer.observe(document, { childList: true, subtree: true })
+  })
+
+  const nextFrame = () => new Promise(resolve => requestAnimationFrame(resolve))
+
+  heading
+    .then(nextFrame)
+    .then(nextFrame)
+    .then(async () => {
+      const paint = performance.now()
+      await loaded
+      await window.__TAURI_INTERNALS__.invoke('paint_report', {
+        timings: {
+          timeOrigin: performance.timeOrigin,
+          paint,
+          domContentLoaded: marks.domContentLoaded,
+          load: marks.load,
+        },
+      })
+    })
+    .catch(err => console.error('first-paint probe failed:', err))
+})()
@@ -0,0 +1,131 @@
+// Custom URI scheme asset serving (BENCHMARK=assets).
+//
+// The page normally comes out of the assets embedded from ../src through
+// Tauri's built-in protocol. In this mode two extra schemes serve generated
+// assets instead:
+//
+//   bench        register_uri_scheme_protocol; the body is built on the
+//                webview's protocol thread
+//   bench-async  register_asynchronous_uri_scheme_protocol; the body is built
+//                on the async runtime's blocking pool and handed back through
+//                the responder
+//
+// An asset is addressed as `<scheme>://localhost/<bytes>-<n>` (or
+// `http://<scheme>.localhost/...` on Windows and Android) and is `bytes` bytes
+// long, at most 10 MB; `n` only keeps URLs distinct. ../src/bench/assets.js
+// fetches BENCHMARK_ASSET_COUNT assets of each size in BENCHMARK_ASSET_SIZES
+// from both schemes and reports the timings through `assets_report`. As a
+// baseline it fetches the embedded fixture of each size that build.rs wrote to
+// ../src/fixtures the same number of times through the built-in protocol.
+
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::http::{header, Request, Response, StatusCode};
+use tauri::{AppHandle, Builder, Runtime, State};
+
+use crate::mode::BenchConfig;
+use crate::report::{self, Report};
+use crate::stats::Summary;
+
+const SCHEME: &str = "bench";
+const ASYNC_SCHEME: &str = "bench-async";
+/// Largest asset served, the largest payload any mode advertises (10 MB).
+const MAX_BYTES: usize = 10 << 20;
+
+/// Register both asset schemes on the builder.
+pub fn register<R: Runtime>(builder: Builder<R>) -> Builder<R> {
+    builder
+        .register_uri_scheme_protocol(SCHEME, |_ctx, request| serve(&request))
+        .register_asynchronous_uri_scheme_protocol(ASYNC_SCHEME, |_ctx, request, responder| {
+            tauri::async_runtime::spawn_blocking(move || responder.respond(serve(&request)));
+        })
+}
+
+/// Build the asset a request asks for: a 404 for a malformed path, a 400 for
+/// a size over `MAX_BYTES`.
+fn serve(request: &Request<Vec<u8>>) -> Response<Cow<'static, [u8]>> {
+    let size = request
+        .uri()
+        .path()
+        .trim_start_matches('/')
+        .split('-')
+        .next()
+        .and_then(|bytes| bytes.parse::<usize>().ok());
+
+    let builder = Response::builder()
+        // The page lives on the built-in protocol's origin.
+        .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+        .header(header::CACHE_CONTROL, "no-store");
+    let response = match size {
+        Some(size) if size <= MAX_BYTES => builder
+            .header(header::CONTENT_TYPE, "application/octet-stream")
+            .body(Cow::Owned(vec![b'x'; size])),
+        Some(_) => builder
+            .status(StatusCode::BAD_REQUEST)
+            .body(Cow::Borrowed(&[][..])),
+        None => builder
+            .status(StatusCode::NOT_FOUND)
+            .body(Cow::Borrowed(&[][..])),
+    };
+    response.expect("static response parts are valid")
+}
+
+/// Fetch timings for one source at one asset size.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetSamples {
+    /// `bench`, `bench-async` or `embedded`.
+    pub source: String,
+    pub bytes: u64,
+    /// Latency of each fetch, issued one after another.
+    pub sequential: Vec<f64>,
+    /// Wall time for the same number of fetches issued all at once.
+    pub parallel_ms: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct AssetResult {
+    source: String,
+    bytes: u64,
+    count: usize,
+    latency_ms: Option<Summary>,
+    parallel_ms: f64,
+    /// Asset bytes delivered per second by the parallel batch.
+    throughput_mb_s: Option<f64>,
+}
+
+/// Final call from the assets driver: report every source and size and quit.
+#[tauri::command]
+pub fn assets_report<R: Runtime>(
+    app: AppHandle<R>,
+    config: State<'_, BenchConfig>,
+    report: State<'_, Report>,
+    results: Vec<AssetSamples>,
+) {
+    let results: Vec<AssetResult> = results
+        .into_iter()
+        .map(|r| {
+            let count = r.sequential.len();
+            AssetResult {
+                throughput_mb_s: (r.parallel_ms > 0.0)
+                    .then(|| count as f64 * r.bytes as f64 / 1e6 / (r.parallel_ms / 1e3)),
+                latency_ms: Summary::from_samples(&r.sequential),
+                source: r.source,
+                bytes: r.bytes,
+                count,
+                parallel_ms: r.parallel_ms,
+            }
+        })
+        .collect();
+    report.emit(
+        "assets",
+        json!({
+            "scenario": "assets",
+            "count": config.asset_count,
+            "results": results,
+        }),
+    );
+    report::finish(&app);
+}
@@ -0,0 +1,221 @@
+// Tauri commands invoked from the benchmark frontend.
+//
+// `update_title` and `echo` are the commands being measured; `bench_config`
+// and `ipc_report` are plumbing for the driver page in ../src/bench/ipc.js,
+// and `paint_report` receives the timings from scripts/first-paint.js.
+//
+// The `payload_*` commands echo large payloads in three encodings for
+// ../src/bench/payload.js, which reports back through `payload_report`.
+//
+// A driver that throws calls `bench_fail`, which ends the run with a
+// scenario error (error.rs) instead of leaving it waiting for a report.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::ipc::{InvokeBody, Request, Response};
+use tauri::{AppHandle, Runtime, State};
+
+use crate::error::Error;
+use crate::histogram::{Histogram, HistogramReport};
+use crate::mode::BenchConfig;
+use crate::report::{self, Report};
+use crate::stats::Summary;
+
+/// Rust mirror of the shared `DATA` payload in ipc.bench.ts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Data {
+    pub title: String,
+    pub timestamp: u64,
+    pub metadata: Metadata,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Metadata {
+    pub source: String,
+    pub priority: u32,
+}
+
+/// Same `{ ok: true }` reply the other frameworks send back.
+#[derive(Debug, Clone, Serialize)]
+pub struct Ack {
+    pub ok: bool,
+}
+
+#[tauri::command]
+pub fn update_title(data: Data) -> Ack {
+    // The payload is fully deserialized into `Data`; that is the cost we want
+    // to measure, so nothing else happens here.
+    let _ = data;
+    Ack { ok: true }
+}
+
+#[tauri::command]
+pub fn echo(value: Value) -> Value {
+    value
+}
+
+#[tauri::command]
+pub fn bench_config(config: State<'_, BenchConfig>) -> BenchConfig {
+    config.inner().clone()
+}
+
+#[tauri::command]
+pub fn bench_fail<R: Runtime>(app: AppHandle<R>, message: String) {
+    report::fail(&app, Error::Scenario(message));
+}
+
+/// Raw round-trip samples for one command, measured in the webview.
+#[derive(Debug, Deserialize)]
+pub struct IpcSamples {
+    pub command: String,
+    pub samples: Vec<f64>,
+}
+
+#[derive(Debug, Serialize)]
+struct IpcResult {
+    command: String,
+    latency_ms: Option<Summary>,
+    latency_histogram: Option<HistogramReport>,
+}
+
+#[derive(Debug, Serialize)]
+struct IpcReport<'a> {
+    scenario: &'static str,
+    iterations: u64,
+    warmup: u64,
+    commands: &'a [IpcResult],
+}
+
+/// Final call from the IPC driver: report the summary and quit. The samples
+/// are only as fine as `timer_resolution_ms`, the page's performance.now()
+/// step.
+#[tauri::command]
+pub fn ipc_report<R: Runtime>(
+    app: AppHandle<R>,
+    config: State<'_, BenchConfig>,
+    report: State<'_, Report>,
+    results: Vec<IpcSamples>,
+    timer_resolution_ms: Option<f64>,
+) {
+    let resolution_ms = timer_resolution_ms.unwrap_or(0.0);
+    let commands: Vec<IpcResult> = results
+        .into_iter()
+        .map(|r| IpcResult {
+            latency_ms: Summary::from_samples(&r.samples),
+            latency_histogram: Histogram::from_samples_at(&r.samples, resolution_ms).report(),
+            command: r.command,
+        })
+        .collect();
+    let ipc = IpcReport {
+        scenario: "ipc",
+        iterations: config.iterations,
+        warmup: config.warmup,
+        commands: &commands,
+    };
+    report.emit("ipc", serde_json::to_value(ipc).unwrap_or(Value::Null));
+    report::finish(&app);
+}
+
+/// Page timings from the first-paint probe, in milliseconds relative to
+/// `performance.timeOrigin` (itself a Unix epoch timestamp).
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaintTimings {
+    pub time_origin: f64,
+    pub paint: f64,
+    pub dom_content_loaded: Option<f64>,
+    pub load: Option<f64>,
+}
+
+/// Called once "Hello World" has been painted: report the timings and quit.
+#[tauri::command]
+pub fn paint_report<R: Runtime>(
+    app: AppHandle<R>,
+    report: State<'_, Report>,
+    timings: PaintTimings,
+) {
+    report.emit(
+        "paint",
+        json!({
+            "time_origin_ms": timings.time_origin,
+            "paint_ms": timings.paint,
+            "dom_content_loaded_ms": timings.dom_content_loaded,
+            "load_ms": timings.load,
+        }),
+    );
+    report::finish(&app);
+}
+
+/// Payload sent as one JSON string.
+#[tauri::command]
+pub fn payload_string(data: String) -> String {
+    data
+}
+
+/// Payload sent as a JSON array of numbers, deserialized into bytes.
+#[tauri::command]
+pub fn payload_bytes(data: Vec<u8>) -> Vec<u8> {
+    data
+}
+
+/// Payload sent as a raw request body (`InvokeBody::Raw`) and returned as a
+/// raw response, skipping JSON on both legs.
+#[tauri::command]
+pub fn payload_raw(request: Request<'_>) -> Result<Response, String> {
+    match request.body() {
+        InvokeBody::Raw(bytes) => Ok(Response::new(bytes.clone())),
+        InvokeBody::Json(_) => Err("payload_raw expects a raw (ArrayBuffer) body".into()),
+    }
+}
+
+/// Round-trip samples for one encoding at one payload size.
+#[derive(Debug, Deserialize)]
+pub struct PayloadSamples {
+    pub form: String,
+    pub bytes: u64,
+    pub samples: Vec<f64>,
+}
+
+#[derive(Debug, Serialize)]
+struct PayloadResult {
+    form: String,
+    bytes: u64,
+    latency_ms: Option<Summary>,
+    /// Payload bytes moved per second in each direction, from mean latency.
+    throughput_mb_s: Option<f64>,
+}
+
+/// Final call from the payload driver: report per-size results and quit.
+#[tauri::command]
+pub fn payload_report<R: Runtime>(
+    app: AppHandle<R>,
+    config: State<'_, BenchConfig>,
+    report: State<'_, Report>,
+    results: Vec<PayloadSamples>,
+) {
+    let results: Vec<PayloadResult> = results
+        .into_iter()
+        .map(|r| {
+            let latency = Summary::from_samples(&r.samples);
+            let throughput = latency
+                .as_ref()
+                .filter(|l| l.mean > 0.0)
+                .map(|l| r.bytes as f64 / 1e6 / (l.mean / 1e3));
+            PayloadResult {
+                form: r.form,
+                bytes: r.bytes,
+                latency_ms: latency,
+                throughput_mb_s: throughput,
+            }
+        })
+        .collect();
+    report.emit(
+        "payload",
+        json!({
+            "scenario": "payload",
+            "iterations": config.payload_iterations,
+            "results": results,
+        }),
+    );
+    report::finish(&app);
+}
@@ -0,0 +1,218 @@
+// Remote control over stdin (BENCHMARK=control).
+//
+// Instead of running one scenario and exiting, the app loads index.html,
+// prints {"event":"listening"} and then reads one JSON request per line from
+// stdin, answering each with one JSON line on stdout:
+//
+//   {"id": 1, "cmd": "run", "scenario": "ipc"}
+//       -> {"id": 1, "ok": true, "result": {"ipc": {...}}}
+//   {"id": 2, "cmd": "memory"}
+//       -> {"id": 2, "ok": true, "result": {"processes": [...], "total": {...}}}
+//   {"id": 3, "cmd": "quit"}
+//       -> {"id": 3, "ok": true, "result": null}, then the usual exit
+//
+// `id` is optional and echoed back as given; a failed request answers
+// {"id": .., "ok": false, "error": ".."}, including a scenario that fails
+// (error.rs) instead of ending the instance. A scenario is any BENCHMARK value
+// that neither depends on a fresh process ("1", "synthetic") nor exits on
+// its own ("scenario"): the main
+// window is navigated to the scenario's page and the report is redirected
+// (Report::redirect) so its sections come back as the result once it
+// finishes, with the instance left running and warm for the next one.
+// Closing stdin quits as well.
+//
+// A scenario that times out keeps the report redirected into a sink nobody
+// answers from, so whatever it still reports can't reach stdout or end the
+// instance; further runs are refused until it has finished.
+
+use std::io::{self, BufRead};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::memory;
+use crate::mode::Mode;
+use crate::report::{self, Outcome, Report};
+
+/// How long a scenario's page may take to load.
+const LOAD_TIMEOUT: Duration = Duration::from_secs(30);
+/// How long a scenario may take to finish once loaded.
+const SCENARIO_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Page-load notifications for a scenario that is starting; kept in Tauri
+/// state.
+#[derive(Default)]
+pub struct Control {
+    loaded: Mutex<Option<Sender<()>>>,
+    /// A scenario that timed out but hasn't finished yet, and what it still
+    /// reports.
+    stale: Mutex<Option<(String, Receiver<Outcome>)>>,
+}
+
+impl Control {
+    /// Err while a timed-out scenario is still running.
+    fn check_stale(&self) -> Result<(), String> {
+        let mut stale = self.stale.lock().unwrap();
+        if let Some((scenario, rx)) = stale.as_ref() {
+            loop {
+                match rx.try_recv() {
+                    Ok(Outcome::Section(..)) => {}
+                    Ok(_) | Err(TryRecvError::Disconnected) => break,
+                    Err(TryRecvError::Empty) => {
+                        return Err(format!("{scenario} timed out and is still running"))
+                    }
+                }
+            }
+        }
+        *stale = None;
+        Ok(())
+    }
+}
+
+/// Called for every finished page load of the main window.
+pub fn page_loaded<R: Runtime>(app: &AppHandle<R>) {
+    if let Some(tx) = app.state::<Control>().loaded.lock().unwrap().as_ref() {
+        let _ = tx.send(());
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Request {
+    #[serde(default)]
+    id: Value,
+    #[serde(flatten)]
+    command: Command,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+enum Command {
+    Run { scenario: String },
+    Memory,
+    Quit,
+}
+
+/// Start answering requests from stdin. `on_loaded` is what the app runs
+/// once a mode's page has loaded.
+pub fn listen<R: Runtime>(app: &AppHandle<R>, on_loaded: fn(&AppHandle<R>, Mode)) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        println!("{}", json!({ "event": "listening" }));
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (id, result, quit) = match serde_json::from_str::<Request>(&line) {
+                Ok(Request { id, command }) => {
+                    let quit = matches!(command, Command::Quit);
+                    (id, handle(&app, command, on_loaded), quit)
+                }
+                Err(e) => (Value::Null, Err(format!("bad request: {e}")), false),
+            };
+            let reply = match result {
+                Ok(result) => json!({ "id": id, "ok": true, "result": result }),
+                Err(error) => json!({ "id": id, "ok": false, "error": error }),
+            };
+            println!("{reply}");
+            if quit {
+                break;
+            }
+        }
+        // Quitting ends a timed-out scenario too.
+        app.state::<Report>().redirect(None);
+        report::finish(&app);
+    });
+}
+
+fn handle<R: Runtime>(
+    app: &AppHandle<R>,
+    command: Command,
+    on_loaded: fn(&AppHandle<R>, Mode),
+) -> Result<Value, String> {
+    match command {
+        Command::Run { scenario } => run(app, &scenario, on_loaded),
+        Command::Memory => serde_json::to_value(memory::sample_tree()).map_err(|e| e.to_string()),
+        Command::Quit => Ok(Value::Null),
+    }
+}
+
+/// Run one scenario in place and collect the sections it reports.
+pub fn run<R: Runtime>(
+    app: &AppHandle<R>,
+    scenario: &str,
+    on_loaded: fn(&AppHandle<R>, Mode),
+) -> Result<Value, String> {
+    let mode = Mode::from_name(scenario);
+    if matches!(
+        mode,
+        Mode::Interactive | Mode::Startup(_) | Mode::Synthetic | Mode::Scenario | Mode::Control
+    ) {
+        return Err(format!(
+            "scenario {scenario:?} can't run over the control channel"
+        ));
+    }
+    let window = app
+        .get_webview_window("main")
+        .ok_or("the main window is gone")?;
+    let url = window
+        .url()
+        .map_err(|e| e.to_string())?
+        .join(&format!("/{}", mode.page()))
+        .map_err(|e| e.to_string())?;
+
+    let control = app.state::<Control>();
+    control.check_stale()?;
+    let report = app.state::<Report>();
+    let (loaded_tx, loaded_rx) = mpsc::channel();
+    let (tx, rx) = mpsc::channel();
+    *control.loaded.lock().unwrap() = Some(loaded_tx);
+    report.redirect(Some(tx));
+
+    let mut running = false;
+    let result = (|| {
+        window.navigate(url).map_err(|e| e.to_string())?;
+        loaded_rx
+            .recv_timeout(LOAD_TIMEOUT)
+            .map_err(|_| format!("{} did not load within {LOAD_TIMEOUT:?}", mode.page()))?;
+        *control.loaded.lock().unwrap() = None;
+        running = true;
+        on_loaded(app, mode);
+
+        let deadline = Instant::now() + SCENARIO_TIMEOUT;
+        let mut sections = Map::new();
+        loop {
+            match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(Outcome::Section(name, value)) => {
+                    sections.insert(name, value);
+                }
+                Ok(Outcome::Finished) => {
+                    running = false;
+                    return Ok(Value::Object(sections));
+                }
+                Ok(Outcome::Failed(error)) => {
+                    running = false;
+                    return Err(error.to_string());
+                }
+                Err(_) => {
+                    return Err(format!(
+                        "{scenario} did not finish within {SCENARIO_TIMEOUT:?}"
+                    ))
+                }
+            }
+        }
+    })();
+
+    *control.loaded.lock().unwrap() = None;
+    if running {
+        // Leave the sink in place until the scenario catches up.
+        *control.stale.lock().unwrap() = Some((scenario.to_owned(), rx));
+    } else {
+        report.redirect(None);
+    }
+    result
+}
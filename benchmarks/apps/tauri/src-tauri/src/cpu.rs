@@ -0,0 +1,264 @@
+// Idle CPU cost of the app and its WebKit children.
+//
+// A snapshot is taken when the idle window starts and another when it ends;
+// the report is the difference. Two sources are used:
+//
+//   getrusage  user/system time for this process (RUSAGE_SELF) and its
+//              reaped children (RUSAGE_CHILDREN). The WebKit helpers are
+//              still running when we exit, so RUSAGE_CHILDREN stays near zero
+//              and is reported only for completeness.
+//   /proc      utime/stime and thread count for this process and every
+//              descendant (Linux), plus voluntary/involuntary context
+//              switches summed over each process's threads: the counts in
+//              /proc/<pid>/status are the main thread's alone, so they come
+//              from /proc/<pid>/task/<tid>/status. Context switches per
+//              second is the closest available proxy for wakeups; a thread
+//              that exits during the window takes its switches with it.
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct CpuTime {
+    pub user_ms: f64,
+    pub system_ms: f64,
+}
+
+impl CpuTime {
+    fn since(self, earlier: CpuTime) -> CpuTime {
+        CpuTime {
+            user_ms: self.user_ms - earlier.user_ms,
+            system_ms: self.system_ms - earlier.system_ms,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct ProcessCounters {
+    name: String,
+    cpu: CpuTime,
+    /// Voluntary and involuntary context switches by thread id.
+    ctxt_switches: HashMap<u32, (u64, u64)>,
+    threads: u64,
+}
+
+impl ProcessCounters {
+    /// Voluntary and involuntary switches since `start`, per thread so that
+    /// threads started in between count from zero.
+    fn switches_since(&self, start: &ProcessCounters) -> (u64, u64) {
+        self.ctxt_switches
+            .iter()
+            .fold((0, 0), |(voluntary, nonvoluntary), (tid, end)| {
+                let begin = start.ctxt_switches.get(tid).copied().unwrap_or((0, 0));
+                (
+                    voluntary + end.0.saturating_sub(begin.0),
+                    nonvoluntary + end.1.saturating_sub(begin.1),
+                )
+            })
+    }
+}
+
+/// Counters at one point in time.
+pub struct Snapshot {
+    at: Instant,
+    rusage_self: Option<CpuTime>,
+    rusage_children: Option<CpuTime>,
+    processes: Vec<(u32, ProcessCounters)>,
+}
+
+impl Snapshot {
+    pub fn take() -> Self {
+        Snapshot {
+            at: Instant::now(),
+            rusage_self: rusage(Who::SelfProcess),
+            rusage_children: rusage(Who::Children),
+            processes: process_counters(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProcessActivity {
+    pub pid: u32,
+    pub name: String,
+    #[serde(flatten)]
+    pub cpu: CpuTime,
+    pub cpu_percent: f64,
+    pub voluntary_ctxt_switches: u64,
+    pub nonvoluntary_ctxt_switches: u64,
+    pub wakeups_per_s: f64,
+    /// Thread count at the end of the window.
+    pub threads: u64,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ActivityTotal {
+    #[serde(flatten)]
+    pub cpu: CpuTime,
+    pub cpu_percent: f64,
+    pub ctxt_switches: u64,
+    pub wakeups_per_s: f64,
+    pub threads: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RusageDelta {
+    #[serde(rename = "self")]
+    pub self_process: Option<CpuTime>,
+    pub children: Option<CpuTime>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IdleReport {
+    pub duration_ms: f64,
+    pub rusage: RusageDelta,
+    pub processes: Vec<ProcessActivity>,
+    pub total: ActivityTotal,
+}
+
+/// Activity between two snapshots. Processes that appeared in between are
+/// counted from zero.
+pub fn idle_report(before: &Snapshot, after: &Snapshot) -> IdleReport {
+    let secs = after.at.duration_since(before.at).as_secs_f64();
+    let percent = |cpu: CpuTime| {
+        if secs > 0.0 {
+            (cpu.user_ms + cpu.system_ms) / (secs * 1e3) * 100.0
+        } else {
+            0.0
+        }
+    };
+    let per_second = |n: u64| if secs > 0.0 { n as f64 / secs } else { 0.0 };
+
+    let baseline: HashMap<u32, &ProcessCounters> =
+        before.processes.iter().map(|(pid, c)| (*pid, c)).collect();
+    let empty = ProcessCounters::default();
+
+    let mut total = ActivityTotal::default();
+    let processes: Vec<ProcessActivity> = after
+        .processes
+        .iter()
+        .map(|(pid, end)| {
+            let start = baseline.get(pid).copied().unwrap_or(&empty);
+            let cpu = end.cpu.since(start.cpu);
+            let (voluntary, nonvoluntary) = end.switches_since(start);
+
+            total.cpu.user_ms += cpu.user_ms;
+            total.cpu.system_ms += cpu.system_ms;
+            total.ctxt_switches += voluntary + nonvoluntary;
+            total.threads += end.threads;
+
+            ProcessActivity {
+                pid: *pid,
+                name: end.name.clone(),
+                cpu,
+                cpu_percent: percent(cpu),
+                voluntary_ctxt_switches: voluntary,
+                nonvoluntary_ctxt_switches: nonvoluntary,
+                wakeups_per_s: per_second(voluntary + nonvoluntary),
+                threads: end.threads,
+            }
+        })
+        .collect();
+    total.cpu_percent = percent(total.cpu);
+    total.wakeups_per_s = per_second(total.ctxt_switches);
+
+    IdleReport {
+        duration_ms: secs * 1e3,
+        rusage: RusageDelta {
+            self_process: after
+                .rusage_self
+                .zip(before.rusage_self)
+                .map(|(a, b)| a.since(b)),
+            children: after
+                .rusage_children
+                .zip(before.rusage_children)
+                .map(|(a, b)| a.since(b)),
+        },
+        processes,
+        total,
+    }
+}
+
+enum Who {
+    SelfProcess,
+    Children,
+}
+
+#[cfg(unix)]
+fn rusage(who: Who) -> Option<CpuTime> {
+    let who = match who {
+        Who::SelfProcess => libc::RUSAGE_SELF,
+        Who::Children => libc::RUSAGE_CHILDREN,
+    };
+    // SAFETY: an all-zero rusage is a valid value and getrusage only writes
+    // into the struct we pass.
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    if unsafe { libc::getrusage(who, &mut usage) } != 0 {
+        return None;
+    }
+    let ms = |tv: libc::timeval| tv.tv_sec as f64 * 1e3 + tv.tv_usec as f64 / 1e3;
+    Some(CpuTime {
+        user_ms: ms(usage.ru_utime),
+        system_ms: ms(usage.ru_stime),
+    })
+}
+
+#[cfg(not(unix))]
+fn rusage(_who: Who) -> Option<CpuTime> {
+    None
+}
+
+/// Counters for this process and its descendants.
+#[cfg(target_os = "linux")]
+fn process_counters() -> Vec<(u32, ProcessCounters)> {
+    use crate::procfs;
+
+    // SAFETY: sysconf has no preconditions.
+    let hz = unsafe { libc::sysconf(libc::_SC_CLK_TCK) }.max(1) as f64;
+    let ticks_ms = |pid: &str, field| procfs::stat_field(pid, field).unwrap_or(0) as f64 * 1e3 / hz;
+
+    let me = std::process::id();
+    std::iter::once(me)
+        .chain(procfs::descendants(me))
+        .map(|pid| {
+            let key = pid.to_string();
+            let status = std::fs::read_to_string(format!("/proc/{pid}/status")).unwrap_or_default();
+            let fields = procfs::kb_fields(&status);
+            let get = |name| fields.get(name).copied().unwrap_or(0);
+            let counters = ProcessCounters {
+                name: procfs::comm(pid),
+                // Fields 14 and 15 are utime and stime, in clock ticks.
+                cpu: CpuTime {
+                    user_ms: ticks_ms(&key, 14),
+                    system_ms: ticks_ms(&key, 15),
+                },
+                ctxt_switches: procfs::tasks(pid)
+                    .into_iter()
+                    .map(|tid| {
+                        let status =
+                            std::fs::read_to_string(format!("/proc/{pid}/task/{tid}/status"))
+                                .unwrap_or_default();
+                        let fields = procfs::kb_fields(&status);
+                        let get = |name| fields.get(name).copied().unwrap_or(0);
+                        (
+                            tid,
+                            (
+                                get("voluntary_ctxt_switches"),
+                                get("nonvoluntary_ctxt_switches"),
+                            ),
+                        )
+                    })
+                    .collect(),
+                threads: get("Threads"),
+            };
+            (pid, counters)
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_counters() -> Vec<(u32, ProcessCounters)> {
+    Vec::new()
+}
@@ -0,0 +1,142 @@
+// Failures and exit codes.
+//
+// Anything that ends a benchmark run early is an `Error`. Instead of "ready"
+// the app writes one JSON record to stderr and exits with the kind's code, so
+// the harness can tell a broken build from a crashed webview from a hang
+// without parsing messages:
+//
+//   {"event":"error","kind":"timeout","exit_code":8,"message":"..."}
+//
+// In JSON-report mode the same record is also the report's "error" section.
+// The codes are stable; a new kind gets a new number.
+//
+//   0  success, "ready" was printed
+//   2  config      bad or missing configuration (e.g. BENCHMARK_SCENARIO)
+//   3  setup       building the app failed
+//   4  window      creating a window failed
+//   5  page_load   a page failed to load, or navigating to it failed (Linux)
+//   6  webview     the web content process crashed or was killed (Linux)
+//   7  scenario    a scenario step failed
+//   8  timeout     a page or scenario step did not finish in time
+//   9  stalled     a phase of the run overran its deadline (watchdog.rs)
+//
+// Page-load failures and web process crashes are reported by WebKitGTK (see
+// `watch`), so codes 5 and 6 are Linux only. On other platforms the run
+// stalls instead and the watchdog ends it with code 9.
+
+use std::fmt;
+
+use serde_json::{json, Value};
+use tauri::{Runtime, WebviewWindow};
+
+#[derive(Debug, Clone)]
+pub enum Error {
+    Config(String),
+    Setup(String),
+    Window(String),
+    PageLoad(String),
+    Webview(String),
+    Scenario(String),
+    Timeout(String),
+    Stalled(String),
+}
+
+impl Error {
+    /// Identifier used in the error record.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::Config(_) => "config",
+            Error::Setup(_) => "setup",
+            Error::Window(_) => "window",
+            Error::PageLoad(_) => "page_load",
+            Error::Webview(_) => "webview",
+            Error::Scenario(_) => "scenario",
+            Error::Timeout(_) => "timeout",
+            Error::Stalled(_) => "stalled",
+        }
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Config(_) => 2,
+            Error::Setup(_) => 3,
+            Error::Window(_) => 4,
+            Error::PageLoad(_) => 5,
+            Error::Webview(_) => 6,
+            Error::Scenario(_) => 7,
+            Error::Timeout(_) => 8,
+            Error::Stalled(_) => 9,
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            Error::Config(message)
+            | Error::Setup(message)
+            | Error::Window(message)
+            | Error::PageLoad(message)
+            | Error::Webview(message)
+            | Error::Scenario(message)
+            | Error::Timeout(message)
+            | Error::Stalled(message) => message,
+        }
+    }
+
+    /// The record written to stderr.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "event": "error",
+            "kind": self.kind(),
+            "exit_code": self.exit_code(),
+            "message": self.message(),
+        })
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.kind(), self.message())
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Report `error` and exit right away. Only for failures before the event
+/// loop runs; afterwards use `report::fail` so teardown and the report
+/// still happen.
+pub fn exit(error: Error) -> ! {
+    eprintln!("{}", error.to_json());
+    std::process::exit(error.exit_code())
+}
+
+/// Fail the run if `window`'s page fails to load or its web process dies.
+pub fn watch<R: Runtime>(window: &WebviewWindow<R>) {
+    #[cfg(target_os = "linux")]
+    {
+        let app = tauri::Manager::app_handle(window).clone();
+        let _ = window.with_webview(move |webview| {
+            use webkit2gtk::{NetworkError, PolicyError, WebViewExt};
+
+            let webview = webview.inner();
+            let on_failure = app.clone();
+            webview.connect_load_failed(move |_, _, uri, error| {
+                // Superseded loads (a navigation replacing another) are not
+                // failures.
+                if !error.matches(NetworkError::Cancelled)
+                    && !error.matches(PolicyError::FrameLoadInterruptedByPolicyChange)
+                {
+                    crate::report::fail(&on_failure, Error::PageLoad(format!("{uri}: {error}")));
+                }
+                false
+            });
+            webview.connect_web_process_terminated(move |_, reason| {
+                crate::report::fail(
+                    &app,
+                    Error::Webview(format!("web process terminated: {reason:?}")),
+                );
+            });
+        });
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = window;
+}
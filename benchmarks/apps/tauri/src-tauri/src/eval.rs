@@ -0,0 +1,124 @@
+// JavaScript evaluation round trip (BENCHMARK=eval).
+//
+// Craft hands results to the page by evaluating JS; in Tauri that is
+// `WebviewWindow::eval`. Once index.html has loaded, a background thread
+// evals one script at a time into the main window. Each script carries a
+// string literal padded to the size under test and calls straight back into
+// `eval_ack` with its id and the literal's length, so one sample covers
+// Rust -> eval -> JS -> invoke -> Rust. Every size in BENCHMARK_EVAL_SIZES is
+// run BENCHMARK_EVAL_ITERATIONS times after a few untimed warm-up rounds.
+
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use serde_json::json;
+use tauri::{AppHandle, Manager, State, WebviewWindow};
+
+use crate::error::Error;
+use crate::mode::BenchConfig;
+use crate::report::{self, Report};
+use crate::stats::Summary;
+
+const WARMUP: u64 = 5;
+/// How long to wait for a script to call back before counting it lost.
+const ACK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Where `eval_ack` forwards acknowledgements; kept in Tauri state.
+pub struct EvalAcks(Mutex<Option<Sender<Ack>>>);
+
+impl EvalAcks {
+    pub fn new() -> Self {
+        EvalAcks(Mutex::new(None))
+    }
+}
+
+struct Ack {
+    id: u64,
+    len: u64,
+}
+
+/// Called by every evaluated script.
+#[tauri::command]
+pub fn eval_ack(acks: State<'_, EvalAcks>, id: u64, len: u64) {
+    if let Some(tx) = acks.0.lock().unwrap().as_ref() {
+        let _ = tx.send(Ack { id, len });
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct EvalResult {
+    bytes: u64,
+    failures: u64,
+    latency_ms: Option<Summary>,
+}
+
+fn script(id: u64, padding: &str) -> String {
+    format!(
+        "(() => {{ const data = '{padding}'; \
+         window.__TAURI_INTERNALS__.invoke('eval_ack', {{ id: {id}, len: data.length }}) }})()"
+    )
+}
+
+/// Eval one script and wait for its acknowledgement; returns the round trip
+/// in milliseconds.
+fn round_trip(window: &WebviewWindow, acks: &Receiver<Ack>, id: u64, padding: &str) -> Option<f64> {
+    let script = script(id, padding);
+    let start = Instant::now();
+    window.eval(script).ok()?;
+    loop {
+        let ack = acks
+            .recv_timeout(ACK_TIMEOUT.saturating_sub(start.elapsed()))
+            .ok()?;
+        // Acks from scripts that already timed out are skipped.
+        if ack.id == id {
+            return (ack.len == padding.len() as u64).then(|| start.elapsed().as_secs_f64() * 1e3);
+        }
+    }
+}
+
+/// Run every size against the main window and report. Blocks, so call it off
+/// the main thread.
+pub fn run(app: &AppHandle, config: &BenchConfig) {
+    let Some(window) = app.get_webview_window("main") else {
+        return report::fail(app, Error::Window("the main window is gone".into()));
+    };
+    let (tx, rx) = mpsc::channel();
+    *app.state::<EvalAcks>().0.lock().unwrap() = Some(tx);
+
+    let mut id = 0;
+    let mut results = Vec::new();
+    for &bytes in &config.eval_sizes {
+        let padding = "x".repeat(bytes as usize);
+        for _ in 0..WARMUP {
+            id += 1;
+            round_trip(&window, &rx, id, &padding);
+        }
+
+        let mut samples = Vec::new();
+        let mut failures = 0;
+        for _ in 0..config.eval_iterations {
+            id += 1;
+            match round_trip(&window, &rx, id, &padding) {
+                Some(ms) => samples.push(ms),
+                None => failures += 1,
+            }
+        }
+        results.push(EvalResult {
+            bytes,
+            failures,
+            latency_ms: Summary::from_samples(&samples),
+        });
+    }
+
+    app.state::<Report>().emit(
+        "eval",
+        json!({
+            "scenario": "eval",
+            "iterations": config.eval_iterations,
+            "results": results,
+        }),
+    );
+    report::finish(app);
+}
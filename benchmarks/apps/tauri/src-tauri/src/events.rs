@@ -0,0 +1,151 @@
+// Rust -> JS event streaming (BENCHMARK=events).
+//
+// Craft pushes results into the page with evaluateJavaScript; Tauri's
+// equivalent is `Emitter::emit`. Once ../src/bench/events.js has registered
+// its listeners it calls `events_start`, and a background thread emits
+// BENCHMARK_EVENT_RATE `bench:tick` events per second for
+// BENCHMARK_DURATION_MS, followed by one `bench:done`. The page records the
+// sequence number, arrival time and one-way latency of every tick and hands
+// them back through `events_report`, where delivered rate, drops, ordering
+// and jitter are worked out, and latency and inter-arrival times are also
+// recorded in histograms (see histogram.rs) for their tails.
+
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Runtime, State};
+
+use crate::histogram::{Histogram, HistogramReport};
+use crate::mode::BenchConfig;
+use crate::report::{self, Report};
+use crate::stats::{self, Summary};
+use crate::timing;
+
+const TICK: &str = "bench:tick";
+const DONE: &str = "bench:done";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Tick {
+    seq: u64,
+    /// Unix epoch milliseconds, for one-way latency in the page.
+    sent_at: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Done {
+    sent: u64,
+    elapsed_ms: f64,
+}
+
+/// Start emitting; called by the page once it is listening.
+#[tauri::command]
+pub fn events_start<R: Runtime>(app: AppHandle<R>, config: State<'_, BenchConfig>) {
+    let rate = config.event_rate.max(1);
+    let total = rate * config.duration_ms / 1000;
+    let period = Duration::from_secs_f64(1.0 / rate as f64);
+
+    std::thread::spawn(move || {
+        let start = Instant::now();
+        for seq in 0..total {
+            // Emit on a fixed schedule so a slow emit doesn't lower the rate.
+            let due = start + period.mul_f64(seq as f64);
+            std::thread::sleep(due.saturating_duration_since(Instant::now()));
+            let _ = app.emit(
+                TICK,
+                Tick {
+                    seq,
+                    sent_at: timing::epoch_ms(),
+                },
+            );
+        }
+        let _ = app.emit(
+            DONE,
+            Done {
+                sent: total,
+                elapsed_ms: start.elapsed().as_secs_f64() * 1e3,
+            },
+        );
+    });
+}
+
+/// What the page saw, one entry per received tick.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventSamples {
+    pub sent: u64,
+    pub seqs: Vec<u64>,
+    /// `performance.now()` at arrival.
+    pub arrivals: Vec<f64>,
+    /// Arrival minus `sentAt`, both Unix epoch milliseconds.
+    pub latencies: Vec<f64>,
+    /// Step of the page's performance.now(), which times the arrivals.
+    #[serde(default)]
+    pub timer_resolution_ms: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EventStats {
+    pub rate_hz: u64,
+    pub sent: u64,
+    pub received: usize,
+    pub dropped: u64,
+    pub duplicates: usize,
+    pub out_of_order: usize,
+    /// Received events per second between the first and last arrival.
+    pub delivered_rate_hz: Option<f64>,
+    pub expected_interval_ms: f64,
+    pub inter_arrival_ms: Option<Summary>,
+    /// Standard deviation of the inter-arrival times.
+    pub jitter_ms: Option<f64>,
+    pub latency_ms: Option<Summary>,
+    pub inter_arrival_histogram: Option<HistogramReport>,
+    pub latency_histogram: Option<HistogramReport>,
+}
+
+impl EventStats {
+    pub fn from_samples(rate_hz: u64, samples: &EventSamples) -> Self {
+        let received = samples.seqs.len();
+        let resolution_ms = samples.timer_resolution_ms;
+        let unique: HashSet<u64> = samples.seqs.iter().copied().collect();
+        let out_of_order = samples.seqs.windows(2).filter(|w| w[1] < w[0]).count();
+        let intervals: Vec<f64> = samples.arrivals.windows(2).map(|w| w[1] - w[0]).collect();
+        let span_ms = match (samples.arrivals.first(), samples.arrivals.last()) {
+            (Some(first), Some(last)) if last > first => Some(last - first),
+            _ => None,
+        };
+
+        EventStats {
+            rate_hz,
+            sent: samples.sent,
+            received,
+            dropped: samples.sent.saturating_sub(unique.len() as u64),
+            duplicates: received - unique.len(),
+            out_of_order,
+            delivered_rate_hz: span_ms.map(|ms| (received - 1) as f64 / (ms / 1e3)),
+            expected_interval_ms: 1e3 / rate_hz.max(1) as f64,
+            inter_arrival_ms: Summary::from_samples(&intervals),
+            jitter_ms: stats::std_dev(&intervals),
+            latency_ms: Summary::from_samples(&samples.latencies),
+            inter_arrival_histogram: Histogram::from_samples_at(&intervals, resolution_ms).report(),
+            latency_histogram: Histogram::from_samples_at(&samples.latencies, resolution_ms)
+                .report(),
+        }
+    }
+}
+
+/// Final call from the events driver: report delivery stats and quit.
+#[tauri::command]
+pub fn events_report<R: Runtime>(
+    app: AppHandle<R>,
+    config: State<'_, BenchConfig>,
+    report: State<'_, Report>,
+    samples: EventSamples,
+) {
+    let stats = EventStats::from_samples(config.event_rate.max(1), &samples);
+    report.emit("events", serde_json::to_value(stats).unwrap_or(Value::Null));
+    report::finish(&app);
+}
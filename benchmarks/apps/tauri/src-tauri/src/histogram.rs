@@ -0,0 +1,161 @@
+// HDR-style latency histogram.
+//
+// Samples are recorded in whole microseconds into log-linear buckets: values
+// below SUB_BUCKETS get a bucket each, and every power of two above that is
+// split into SUB_BUCKETS equal buckets. Any value is therefore known to
+// within 1/SUB_BUCKETS (under 1%) of itself while the bucket count grows
+// only with the logarithm of the range. Percentiles are read off the
+// cumulative counts and reported as the upper edge of the bucket they land
+// in, capped at the largest value seen, as HdrHistogram does, so a tail is
+// never rounded down.
+//
+// Samples timed in the webview come from performance.now(), which the engine
+// coarsens (often to 100 µs or more). For those the page measures the step
+// it actually sees and no bucket is made narrower than that, rounded up to a
+// power of two of microseconds so the two bucket layouts line up; the step
+// itself is reported as `resolution_ms`.
+//
+// The report carries p50/p90/p99/p99.9/max and every non-empty bucket as
+// `[upper_ms, count]`, enough to redraw the distribution offline.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+const SUB_BUCKET_BITS: u32 = 7;
+const SUB_BUCKETS: u64 = 1 << SUB_BUCKET_BITS;
+
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    /// Bucket lower bound (µs) -> count.
+    buckets: BTreeMap<u64, u64>,
+    count: u64,
+    min: u64,
+    max: u64,
+    /// Resolution of the clock the samples came from.
+    resolution_ms: f64,
+    /// Narrowest bucket (µs), a power of two.
+    floor: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HistogramReport {
+    pub count: u64,
+    pub min: f64,
+    pub p50: f64,
+    pub p90: f64,
+    pub p99: f64,
+    pub p99_9: f64,
+    pub max: f64,
+    /// Resolution of the clock the samples came from.
+    pub resolution_ms: f64,
+    /// Non-empty buckets as `[upper edge in ms, count]`, in order.
+    pub buckets: Vec<(f64, u64)>,
+}
+
+/// Lower bound and width (µs) of the bucket holding `us`, no narrower than
+/// `floor`.
+fn bucket(us: u64, floor: u64) -> (u64, u64) {
+    let (lower, width) = if us < SUB_BUCKETS {
+        (us, 1)
+    } else {
+        let shift = (63 - us.leading_zeros()) - SUB_BUCKET_BITS;
+        ((us >> shift) << shift, 1 << shift)
+    };
+    if width >= floor {
+        (lower, width)
+    } else {
+        (us / floor * floor, floor)
+    }
+}
+
+fn ms(us: u64) -> f64 {
+    us as f64 / 1e3
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Histogram::with_resolution(1e-3)
+    }
+}
+
+impl Histogram {
+    /// An empty histogram for samples from a clock with steps of
+    /// `resolution_ms`.
+    pub fn with_resolution(resolution_ms: f64) -> Self {
+        let resolution_ms = resolution_ms.max(1e-3);
+        Histogram {
+            buckets: BTreeMap::new(),
+            count: 0,
+            min: 0,
+            max: 0,
+            resolution_ms,
+            floor: ((resolution_ms * 1e3).round() as u64).next_power_of_two(),
+        }
+    }
+
+    /// Histogram of millisecond samples. Negative values (clock skew between
+    /// sender and receiver) are recorded as zero.
+    pub fn from_samples(samples: &[f64]) -> Self {
+        Self::from_samples_at(samples, 1e-3)
+    }
+
+    /// Histogram of millisecond samples taken with a clock that only moves in
+    /// steps of `resolution_ms`, such as the webview's performance.now().
+    pub fn from_samples_at(samples: &[f64], resolution_ms: f64) -> Self {
+        let mut histogram = Histogram::with_resolution(resolution_ms);
+        for &sample in samples {
+            histogram.record((sample.max(0.0) * 1e3).round() as u64);
+        }
+        histogram
+    }
+
+    pub fn record(&mut self, us: u64) {
+        let (lower, _) = bucket(us, self.floor);
+        *self.buckets.entry(lower).or_insert(0) += 1;
+        self.min = if self.count == 0 {
+            us
+        } else {
+            self.min.min(us)
+        };
+        self.max = self.max.max(us);
+        self.count += 1;
+    }
+
+    /// Upper edge (µs) of the bucket holding the `q` quantile, capped at the
+    /// maximum.
+    fn value_at(&self, q: f64) -> u64 {
+        let rank = ((q * self.count as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (&lower, &count) in &self.buckets {
+            seen += count;
+            if seen >= rank {
+                let (_, width) = bucket(lower, self.floor);
+                return (lower + width - 1).min(self.max);
+            }
+        }
+        self.max
+    }
+
+    /// Percentiles and buckets in milliseconds. `None` when empty.
+    pub fn report(&self) -> Option<HistogramReport> {
+        (self.count > 0).then(|| HistogramReport {
+            count: self.count,
+            min: ms(self.min),
+            p50: ms(self.value_at(0.50)),
+            p90: ms(self.value_at(0.90)),
+            p99: ms(self.value_at(0.99)),
+            p99_9: ms(self.value_at(0.999)),
+            max: ms(self.max),
+            resolution_ms: self.resolution_ms,
+            buckets: self
+                .buckets
+                .iter()
+                .map(|(&lower, &count)| {
+                    let (_, width) = bucket(lower, self.floor);
+                    (ms(lower + width - 1), count)
+                })
+                .collect(),
+        })
+    }
+}
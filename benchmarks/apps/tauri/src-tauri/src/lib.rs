@@ -0,0 +1,329 @@
+// Tauri Hello World - Benchmark App
+//
+// Minimal Tauri app for startup time measurement. The app lives in this
+// library (`run`) so benches and tests can reach its commands; main.rs only
+// calls it.
+// Build: cd src-tauri && cargo build --release
+// Run:   ./target/release/tauri-hello-world
+//
+// Set BENCHMARK=1 to auto-quit after app is initialized. By default "ready"
+// is printed when the main window's page has finished loading; set
+// BENCHMARK_READY=paint to wait for the first paint of "Hello World"
+// (reported by scripts/first-paint.js) or BENCHMARK_READY=timer for the
+// old fixed 50 ms after setup().
+//
+// Set BENCHMARK=ipc to measure real invoke round trips: ipc.html drives the
+// commands in commands.rs and reports its samples back before quitting.
+// Set BENCHMARK=payload to see how invoke scales from 1 KB to 10 MB with JSON
+// strings, JSON byte arrays and raw bodies (payload.html).
+// Set BENCHMARK=events to stream events from Rust into the page at a fixed
+// rate and see how many arrive, in what order and how evenly (events.rs).
+// Set BENCHMARK=stream to compare ipc::Channel with emit for ordered chunk
+// streams (stream.rs).
+// Set BENCHMARK=assets to time fetching generated assets from custom URI
+// schemes against the embedded ones (assets.rs).
+// Set BENCHMARK=synthetic to time startup with the many-asset frontend that
+// build.rs generates when BENCHMARK_SYNTHETIC_ASSETS is set at build time.
+// Set BENCHMARK=windows to open more windows after startup and see what each
+// one costs in time and memory (windows.rs).
+// Set BENCHMARK=window-ops to time show/hide/toggle/minimize/close and the
+// title, size and position setters on a second window (window_ops.rs).
+// Set BENCHMARK=eval to time WebviewWindow::eval round trips that call back
+// into a command, for scripts of increasing size (eval.rs).
+// Set BENCHMARK=memory to report PSS/USS for the whole process tree at ready
+// time, or BENCHMARK=memory-series to keep sampling it for a while (memory.rs).
+// Set BENCHMARK=idle to measure what an idle window costs in CPU and wakeups
+// (cpu.rs).
+//
+// Set BENCHMARK=scenario with BENCHMARK_SCENARIO=<file.json> to run a list of
+// steps from a file instead of a fixed mode (scenario.rs, ../scenarios/).
+// Set BENCHMARK=control to keep one instance running and drive it with JSON
+// requests on stdin: run scenarios, sample memory, quit (control.rs).
+// Set BENCHMARK=rendering to print how WebKitGTK renders (version, acceleration
+// policy, display backend) and BENCHMARK_RENDERING=software to force software
+// rendering on GPU-less Linux boxes (rendering.rs).
+// Set BENCHMARK_PROBE_INTERVAL_MS alongside any mode to see how late tasks
+// queued on the main thread run while the scenario is busy (probe.rs).
+//
+// A run that fails prints a JSON error record on stderr instead of "ready"
+// and exits with a code per kind of failure (error.rs); one that hangs is
+// stopped by per-phase deadlines with a partial report (watchdog.rs).
+//
+// Set BENCHMARK_REPORT=json for one JSON document with a timestamp for each
+// startup and shutdown phase (see report.rs). See mode.rs for the full list of switches.
+
+mod assets;
+pub mod commands;
+pub mod control;
+mod cpu;
+pub mod error;
+mod eval;
+mod events;
+pub mod histogram;
+mod memory;
+pub mod mode;
+mod probe;
+#[cfg(target_os = "linux")]
+mod procfs;
+mod rendering;
+pub mod report;
+mod scenario;
+pub mod stats;
+mod stream;
+mod timing;
+pub mod watchdog;
+mod window_ops;
+mod windows;
+
+use std::time::Duration;
+
+use serde_json::json;
+use tauri::webview::PageLoadEvent;
+use tauri::{
+    App, AppHandle, Builder, Manager, RunEvent, Runtime, WebviewUrl, WebviewWindowBuilder,
+};
+
+use error::Error;
+use mode::{BenchConfig, Mode, Ready};
+use report::{Phase, Report};
+
+/// Size of build.rs's synthetic frontend, fixed at build time.
+const SYNTHETIC_ASSETS: &str = env!("SYNTHETIC_ASSETS");
+const SYNTHETIC_ASSET_BYTES: &str = env!("SYNTHETIC_ASSET_BYTES");
+
+/// Injected ahead of the page in `BENCHMARK_READY=paint` mode.
+const FIRST_PAINT_SCRIPT: &str = include_str!("../scripts/first-paint.js");
+
+/// Manage the app's state and register every command on `builder`. Shared
+/// by `run` and the mock-runtime benches and tests, so they dispatch exactly
+/// what the app does.
+pub fn register<R: Runtime>(builder: Builder<R>, report: Report) -> Builder<R> {
+    builder
+        .manage(report)
+        .manage(BenchConfig::from_env())
+        .manage(eval::EvalAcks::new())
+        .manage(probe::Probe::default())
+        .manage(control::Control::default())
+        .manage(scenario::ScenarioReplies::default())
+        .invoke_handler(tauri::generate_handler![
+            commands::update_title,
+            commands::echo,
+            commands::bench_config,
+            commands::bench_fail,
+            commands::ipc_report,
+            commands::paint_report,
+            commands::payload_string,
+            commands::payload_bytes,
+            commands::payload_raw,
+            commands::payload_report,
+            events::events_start,
+            events::events_report,
+            stream::stream_channel,
+            stream::stream_emit,
+            stream::stream_report,
+            assets::assets_report,
+            window_ops::window_open,
+            window_ops::window_show,
+            window_ops::window_hide,
+            window_ops::window_toggle,
+            window_ops::window_minimize,
+            window_ops::window_unminimize,
+            window_ops::window_close,
+            window_ops::window_set_title,
+            window_ops::window_set_size,
+            window_ops::window_set_position,
+            window_ops::window_ops_report,
+            eval::eval_ack,
+            scenario::scenario_reply,
+        ])
+}
+
+/// Build and run the app in the mode chosen by `BENCHMARK`.
+pub fn run() {
+    rendering::init();
+    let mode = Mode::from_env();
+    let report = Report::new(mode);
+    report.mark(Phase::Main);
+
+    let builder = tauri::Builder::default();
+    report.mark(Phase::Builder);
+    // The control channel can run BENCHMARK=assets too.
+    let builder = if matches!(mode, Mode::Assets | Mode::Control) {
+        assets::register(builder)
+    } else {
+        builder
+    };
+
+    let app = register(builder, report)
+        .setup(move |app| {
+            app.state::<Report>().mark(Phase::Setup);
+            watchdog::start(app.handle(), mode, &app.state::<BenchConfig>());
+
+            // The main window is declared with `"create": false` so each mode
+            // can pick the page it loads; everything else comes from config.
+            let mut config = app.config().app.windows[0].clone();
+            config.url = WebviewUrl::App(mode.page().into());
+            // The event loop isn't running yet, so a failure here has nothing
+            // to tear down and exits on the spot.
+            let window = WebviewWindowBuilder::from_config(app.handle(), &config)
+                .unwrap_or_else(|e| error::exit(Error::Window(e.to_string())));
+            let mut window = window.on_page_load(move |window, payload| {
+                if payload.event() != PageLoadEvent::Finished {
+                    return;
+                }
+                if window.state::<Report>().mark(Phase::PageLoaded) {
+                    on_page_loaded(window.app_handle(), mode);
+                }
+                control::page_loaded(window.app_handle());
+            });
+            if mode == Mode::Startup(Ready::Paint) {
+                window = window.initialization_script(FIRST_PAINT_SCRIPT);
+            }
+            let window = window
+                .build()
+                .unwrap_or_else(|e| error::exit(Error::Window(e.to_string())));
+            app.state::<Report>().mark(Phase::WindowCreated);
+            rendering::apply(&window);
+            error::watch(&window);
+
+            if mode == Mode::Startup(Ready::Timer) {
+                let handle = app.handle().clone();
+                // Exit from a spawned thread so the event loop can start briefly.
+                // This gives the window time to be shown before we exit.
+                std::thread::spawn(move || {
+                    // Small yield to let the run-loop tick once (window creation)
+                    std::thread::sleep(Duration::from_millis(50));
+                    report::finish(&handle);
+                });
+            }
+            Ok(())
+        })
+        .build(tauri::generate_context!())
+        .unwrap_or_else(|e| error::exit(Error::Setup(e.to_string())));
+
+    std::process::exit(run_until_exit(app));
+}
+
+/// Run the event loop and return the code the process should exit with.
+///
+/// `AppHandle::exit(code)` doesn't carry the code through Tauri 2's event
+/// loop (the runtime exits 0 whatever was asked), so the code comes from the
+/// report instead: set by `report::fail`, 0 for every other way out.
+pub fn run_until_exit<R: Runtime>(app: App<R>) -> i32 {
+    let handle = app.handle().clone();
+    // Timestamp teardown: ExitRequested fires when `exit()` is called or the
+    // last window closes, Exit once the event loop is done.
+    app.run_return(|app, event| match event {
+        RunEvent::ExitRequested { .. } => {
+            app.state::<Report>().mark(Phase::ExitRequested);
+        }
+        RunEvent::Exit => report::exited(app),
+        _ => {}
+    });
+    handle.state::<Report>().exit_code()
+}
+
+/// Runs once the main window's page has loaded: start whatever the mode
+/// measures from there. Long-running work goes to its own thread so the
+/// event loop keeps turning.
+fn on_page_loaded(app: &AppHandle, mode: Mode) {
+    let config = app.state::<BenchConfig>().inner().clone();
+    if config.probe_interval_ms > 0 {
+        probe::start(app, config.probe_interval_ms);
+    }
+    // The rendering setup only goes anywhere in the JSON report, so other
+    // runs don't query GTK and WebKit before they finish.
+    let describe = mode == Mode::Rendering || app.state::<Report>().is_json();
+    if let Some(window) = app.get_webview_window("main").filter(|_| describe) {
+        let app = app.clone();
+        rendering::describe(&window, move |info| {
+            let report = app.state::<Report>();
+            if mode == Mode::Rendering {
+                report.emit("rendering", info);
+                report::finish(&app);
+            } else {
+                report.annotate("rendering", info);
+            }
+        });
+    }
+    match mode {
+        Mode::Startup(Ready::Load) => report::finish(app),
+        Mode::Synthetic => {
+            let report = app.state::<Report>();
+            let phases = report.phases();
+            let origin = phases.process_start.or(phases.main);
+            report.emit(
+                "synthetic",
+                json!({
+                    "assets": SYNTHETIC_ASSETS.parse::<u64>().ok(),
+                    "asset_bytes": SYNTHETIC_ASSET_BYTES.parse::<u64>().ok(),
+                    "startup_ms": origin.zip(phases.page_loaded).map(|(a, b)| b - a),
+                    "page_load_ms": phases
+                        .window_created
+                        .zip(phases.page_loaded)
+                        .map(|(a, b)| b - a),
+                }),
+            );
+            report::finish(app);
+        }
+        Mode::Memory => {
+            let app = app.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(config.settle_ms));
+                let tree = memory::sample_tree();
+                app.state::<Report>()
+                    .emit("memory", serde_json::to_value(tree).unwrap_or_default());
+                report::finish(&app);
+            });
+        }
+        Mode::MemorySeries => {
+            let app = app.clone();
+            std::thread::spawn(move || {
+                let summary = memory::sample_series(
+                    Duration::from_millis(config.interval_ms),
+                    Duration::from_millis(config.duration_ms),
+                    app.state::<Report>().prints_lines(),
+                );
+                app.state::<Report>().emit(
+                    "memory_series",
+                    serde_json::to_value(summary).unwrap_or_default(),
+                );
+                report::finish(&app);
+            });
+        }
+        Mode::Idle => {
+            let app = app.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(config.settle_ms));
+                let before = cpu::Snapshot::take();
+                std::thread::sleep(Duration::from_millis(config.duration_ms));
+                let idle = cpu::idle_report(&before, &cpu::Snapshot::take());
+                app.state::<Report>()
+                    .emit("idle", serde_json::to_value(idle).unwrap_or_default());
+                report::finish(&app);
+            });
+        }
+        Mode::Windows => {
+            let app = app.clone();
+            std::thread::spawn(move || windows::run(&app, &config));
+        }
+        Mode::Eval => {
+            let app = app.clone();
+            std::thread::spawn(move || eval::run(&app, &config));
+        }
+        Mode::Scenario => {
+            let app = app.clone();
+            std::thread::spawn(move || scenario::run(&app, &config));
+        }
+        Mode::Control => control::listen(app, on_page_loaded),
+        Mode::Interactive
+        | Mode::Startup(_)
+        | Mode::Ipc
+        | Mode::Payload
+        | Mode::Events
+        | Mode::Stream
+        | Mode::Assets
+        | Mode::WindowOps
+        | Mode::Rendering => {}
+    }
+}
@@ -0,0 +1,174 @@
+// Process-tree memory accounting (Linux).
+//
+// RSS counts every resident page a process maps, so summing it across the
+// app and its WebKitWebProcess/WebKitNetworkProcess children counts the
+// shared WebKit libraries once per process. smaps_rollup also gives:
+//
+//   PSS  shared pages divided by the number of processes mapping them, so
+//        totals across a process tree add up fairly
+//   USS  pages private to the process (Private_Clean + Private_Dirty): what
+//        would be freed if only that process exited
+//
+// All values are in kB, like smaps_rollup itself.
+//
+// `sample_series` repeats the measurement on a fixed schedule to expose
+// growth after startup: each sample is printed as a JSON line (legacy output)
+// or kept in the summary's `series` (JSON report, control channel), and the
+// run is summarised by least-squares slopes (kB/s) over the whole series and over
+// its second half, once startup allocations have settled.
+
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::stats;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessMemory {
+    pub pid: u32,
+    pub name: String,
+    pub rss_kb: Option<u64>,
+    pub pss_kb: Option<u64>,
+    pub uss_kb: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Totals {
+    pub rss_kb: u64,
+    pub pss_kb: u64,
+    pub uss_kb: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TreeMemory {
+    /// This process first, then its descendants.
+    pub processes: Vec<ProcessMemory>,
+    pub total: Totals,
+}
+
+/// Sample this process and all of its descendants.
+#[cfg(target_os = "linux")]
+pub fn sample_tree() -> Option<TreeMemory> {
+    use crate::procfs;
+
+    let me = std::process::id();
+    let processes: Vec<ProcessMemory> = std::iter::once(me)
+        .chain(procfs::descendants(me))
+        .map(|pid| {
+            let rollup = std::fs::read_to_string(format!("/proc/{pid}/smaps_rollup"));
+            let fields = rollup.as_deref().map(procfs::kb_fields).unwrap_or_default();
+            let private = fields
+                .get("Private_Clean")
+                .zip(fields.get("Private_Dirty"))
+                .map(|(clean, dirty)| clean + dirty);
+            ProcessMemory {
+                pid,
+                name: procfs::comm(pid),
+                rss_kb: fields.get("Rss").copied(),
+                pss_kb: fields.get("Pss").copied(),
+                uss_kb: private,
+            }
+        })
+        .collect();
+
+    let mut total = Totals::default();
+    for p in &processes {
+        total.rss_kb += p.rss_kb.unwrap_or(0);
+        total.pss_kb += p.pss_kb.unwrap_or(0);
+        total.uss_kb += p.uss_kb.unwrap_or(0);
+    }
+    Some(TreeMemory { processes, total })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn sample_tree() -> Option<TreeMemory> {
+    None
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SeriesSample {
+    /// Milliseconds since sampling started.
+    pub t_ms: f64,
+    pub processes: usize,
+    #[serde(flatten)]
+    pub total: Totals,
+}
+
+/// Growth in kB per second for each metric.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Slopes {
+    pub rss: Option<f64>,
+    pub pss: Option<f64>,
+    pub uss: Option<f64>,
+}
+
+impl Slopes {
+    fn fit(points: &[(f64, Totals)]) -> Self {
+        let fit = |metric: fn(&Totals) -> u64| {
+            let xy: Vec<(f64, f64)> = points.iter().map(|(t, m)| (*t, metric(m) as f64)).collect();
+            stats::slope(&xy)
+        };
+        Slopes {
+            rss: fit(|m| m.rss_kb),
+            pss: fit(|m| m.pss_kb),
+            uss: fit(|m| m.uss_kb),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SeriesSummary {
+    pub samples: usize,
+    pub interval_ms: u64,
+    pub duration_ms: u64,
+    pub first: Option<Totals>,
+    pub last: Option<Totals>,
+    pub slope_kb_per_s: Slopes,
+    pub settled_slope_kb_per_s: Slopes,
+    /// Every sample, unless they were printed as they were taken.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub series: Option<Vec<SeriesSample>>,
+}
+
+/// Sample the process tree every `interval` for `duration` and summarise the
+/// growth. With `print`, each sample is printed as a JSON line as it is
+/// taken; otherwise they are returned in `series`.
+pub fn sample_series(interval: Duration, duration: Duration, print: bool) -> SeriesSummary {
+    let start = Instant::now();
+    let mut points: Vec<(f64, Totals)> = Vec::new();
+    let mut series = Vec::new();
+
+    for tick in 1u32.. {
+        let elapsed = start.elapsed();
+        if let Some(tree) = sample_tree() {
+            let sample = SeriesSample {
+                t_ms: elapsed.as_secs_f64() * 1e3,
+                processes: tree.processes.len(),
+                total: tree.total.clone(),
+            };
+            if print {
+                println!("{}", serde_json::to_string(&sample).unwrap_or_default());
+            } else {
+                series.push(sample);
+            }
+            points.push((elapsed.as_secs_f64(), tree.total));
+        }
+        if elapsed >= duration {
+            break;
+        }
+        // Stay on a fixed schedule so slow samples don't stretch the series.
+        let next = start + interval * tick;
+        std::thread::sleep(next.saturating_duration_since(Instant::now()));
+    }
+
+    SeriesSummary {
+        samples: points.len(),
+        interval_ms: interval.as_millis() as u64,
+        duration_ms: duration.as_millis() as u64,
+        first: points.first().map(|p| p.1.clone()),
+        last: points.last().map(|p| p.1.clone()),
+        slope_kb_per_s: Slopes::fit(&points),
+        settled_slope_kb_per_s: Slopes::fit(&points[points.len() / 2..]),
+        series: (!print).then_some(series),
+    }
+}
@@ -0,0 +1,341 @@
+// Benchmark mode selection.
+//
+// The harness drives the app entirely through environment variables so the
+// same binary can be spawned by every *.bench.ts file:
+//
+//   BENCHMARK=1              Startup: print "ready" and exit once initialized.
+//                            BENCHMARK_READY picks the readiness signal:
+//                              load   (default) the page fired
+//                                     PageLoadEvent::Finished, the same point
+//                                     Electron's did-finish-load marks
+//                              paint  "Hello World" was painted (double rAF
+//                                     after the <h1> exists); paint,
+//                                     DOMContentLoaded and load times are
+//                                     printed as JSON before "ready"
+//                              timer  50 ms after setup(), the original
+//                                     behaviour kept so historical numbers
+//                                     can be reproduced
+//   BENCHMARK=ipc            IPC round-trip: the driver page invokes the
+//                            registered commands BENCHMARK_ITERATIONS times
+//                            (default 1000, after BENCHMARK_WARMUP untimed
+//                            calls) and reports latency.
+//   BENCHMARK=events         Event streaming: Rust emits BENCHMARK_EVENT_RATE
+//                            events per second (default 1000) for
+//                            BENCHMARK_DURATION_MS; events.html reports what
+//                            arrived (rate, drops, ordering, jitter; see
+//                            events.rs).
+//   BENCHMARK=stream         Ordered streaming: BENCHMARK_STREAM_CHUNKS chunks
+//                            (default 1000) of BENCHMARK_STREAM_CHUNK_BYTES
+//                            (default 1024) through an ipc::Channel and then
+//                            through emit; throughput and ordering for both
+//                            (see stream.rs).
+//   BENCHMARK=assets         Asset delivery: BENCHMARK_ASSET_COUNT assets
+//                            (default 50) of each size in BENCHMARK_ASSET_SIZES
+//                            (bytes; default 1 KB to 1 MB) fetched from the
+//                            sync and async custom URI schemes, against
+//                            embedded files of the same sizes (see assets.rs).
+//   BENCHMARK=synthetic      Startup with the synthetic frontend generated by
+//                            build.rs (BENCHMARK_SYNTHETIC_ASSETS files at
+//                            build time): once it has loaded, print the asset
+//                            count with startup and page-load times and exit.
+//   BENCHMARK=windows        Once the main window has loaded, open
+//                            BENCHMARK_WINDOWS more (default 10) and report
+//                            per-window creation and page-load times plus the
+//                            process tree's memory before and after (see
+//                            windows.rs).
+//   BENCHMARK=window-ops     Window-operation latency: show, hide, toggle,
+//                            minimize, close, set_title, set_size and
+//                            set_position on a second window,
+//                            BENCHMARK_WINDOW_OP_ITERATIONS times each
+//                            (default 50), timed until the change is visible
+//                            (see window_ops.rs).
+//   BENCHMARK=eval           JS evaluation round trip: Rust evals scripts of
+//                            each size in BENCHMARK_EVAL_SIZES (bytes; default
+//                            100 B to 1 MB) that call straight back into a
+//                            command, BENCHMARK_EVAL_ITERATIONS times each
+//                            (default 100), and reports latency (see eval.rs).
+//   BENCHMARK=memory         Once the page has loaded (plus
+//                            BENCHMARK_SETTLE_MS, default 0), report
+//                            RSS/PSS/USS for this process and its WebKit
+//                            children (Linux; see memory.rs) and exit.
+//   BENCHMARK=memory-series  Stay alive for BENCHMARK_DURATION_MS (default
+//                            30000) after the page loads, sampling the process
+//                            tree every BENCHMARK_INTERVAL_MS (default 250).
+//                            Each sample is printed as a JSON line; the final
+//                            JSON line holds the growth slopes. In the JSON
+//                            report the samples are its `series` instead.
+//   BENCHMARK=payload        Payload scaling: payload.html round-trips every
+//                            size in BENCHMARK_PAYLOAD_SIZES (bytes, comma
+//                            separated; default 1 KB to 10 MB) through the
+//                            JSON-string, JSON-array and raw-body commands,
+//                            BENCHMARK_PAYLOAD_ITERATIONS times each (default
+//                            20), and reports latency and throughput.
+//   BENCHMARK=idle           Leave the loaded window idle for
+//                            BENCHMARK_DURATION_MS (after BENCHMARK_SETTLE_MS)
+//                            and report CPU time, context switches and thread
+//                            counts for the process tree (see cpu.rs).
+//   BENCHMARK=scenario       Run the steps listed in the JSON file named by
+//                            BENCHMARK_SCENARIO (open windows, invoke
+//                            commands, wait for paint, sample memory, emit
+//                            events, ...) and report them together (see
+//                            scenario.rs and ../scenarios/).
+//   BENCHMARK=rendering      Print the rendering configuration (WebKitGTK
+//                            version, acceleration policy, display backend;
+//                            see rendering.rs) once loaded, and exit.
+//   BENCHMARK=control        Stay running and take requests as JSON lines on
+//                            stdin: run any of the modes above except startup,
+//                            synthetic and scenario in the live instance,
+//                            sample memory, or quit (see control.rs).
+//
+// Anything else (including an unset variable) runs the plain interactive app.
+//
+// BENCHMARK_PROBE_INTERVAL_MS=N works with any mode: from page load until the
+// scenario finishes, a task is queued on the main thread every N ms and its
+// scheduling delay recorded (see probe.rs).
+//
+// BENCHMARK_RENDERING=software forces software rendering on Linux (see
+// rendering.rs); the rendering setup is part of every JSON report.
+//
+// BENCHMARK_DEADLINE_WINDOW_MS, BENCHMARK_DEADLINE_PAGE_LOAD_MS and
+// BENCHMARK_DEADLINE_SCENARIO_MS bound each phase of a benchmark run; one
+// that overruns ends it with a partial report (see watchdog.rs).
+//
+// BENCHMARK_REPORT=json collects phase timestamps and results into a single
+// JSON document instead (see report.rs).
+
+use std::env;
+
+use serde::Serialize;
+
+/// What the app should do after startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Normal app: show the window and keep running.
+    Interactive,
+    /// Auto-quit once the app is ready.
+    Startup(Ready),
+    /// Real webview -> Rust -> webview round trips.
+    Ipc,
+    /// Process-tree memory at ready time.
+    Memory,
+    /// Process-tree memory sampled over time.
+    MemorySeries,
+    /// CPU and wakeups while sitting idle.
+    Idle,
+    /// IPC latency and throughput across payload sizes and encodings.
+    Payload,
+    /// Rust -> JS event delivery.
+    Events,
+    /// Channel vs emit streaming.
+    Stream,
+    /// Generated assets from custom URI schemes.
+    Assets,
+    /// Startup with build.rs's synthetic frontend.
+    Synthetic,
+    /// Cost of opening more windows.
+    Windows,
+    /// Latency of window operations.
+    WindowOps,
+    /// Rust -> eval -> JS -> invoke -> Rust round trips.
+    Eval,
+    /// Steps from a scenario file.
+    Scenario,
+    /// Rendering configuration only.
+    Rendering,
+    /// Stay up and take scenarios from the control channel.
+    Control,
+}
+
+impl Mode {
+    pub fn from_env() -> Self {
+        Mode::from_name(&env::var("BENCHMARK").unwrap_or_default())
+    }
+
+    /// Mode for a `BENCHMARK` value; anything unknown is `Interactive`.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "1" => Mode::Startup(Ready::from_env()),
+            "ipc" => Mode::Ipc,
+            "memory" => Mode::Memory,
+            "memory-series" => Mode::MemorySeries,
+            "idle" => Mode::Idle,
+            "payload" => Mode::Payload,
+            "events" => Mode::Events,
+            "stream" => Mode::Stream,
+            "assets" => Mode::Assets,
+            "synthetic" => Mode::Synthetic,
+            "windows" => Mode::Windows,
+            "window-ops" => Mode::WindowOps,
+            "eval" => Mode::Eval,
+            "scenario" => Mode::Scenario,
+            "rendering" => Mode::Rendering,
+            "control" => Mode::Control,
+            _ => Mode::Interactive,
+        }
+    }
+
+    /// Identifier used in the JSON report.
+    pub fn name(self) -> &'static str {
+        match self {
+            Mode::Interactive => "interactive",
+            Mode::Startup(_) => "startup",
+            Mode::Ipc => "ipc",
+            Mode::Memory => "memory",
+            Mode::MemorySeries => "memory-series",
+            Mode::Idle => "idle",
+            Mode::Payload => "payload",
+            Mode::Events => "events",
+            Mode::Stream => "stream",
+            Mode::Assets => "assets",
+            Mode::Synthetic => "synthetic",
+            Mode::Windows => "windows",
+            Mode::WindowOps => "window-ops",
+            Mode::Eval => "eval",
+            Mode::Scenario => "scenario",
+            Mode::Rendering => "rendering",
+            Mode::Control => "control",
+        }
+    }
+
+    /// Frontend page loaded into the main window for this mode.
+    pub fn page(self) -> &'static str {
+        match self {
+            Mode::Ipc => "ipc.html",
+            Mode::Payload => "payload.html",
+            Mode::Events => "events.html",
+            Mode::Stream => "stream.html",
+            Mode::Assets => "assets.html",
+            Mode::Synthetic => "synthetic/index.html",
+            Mode::WindowOps => "window-ops.html",
+            Mode::Scenario => "scenario.html",
+            Mode::Interactive
+            | Mode::Startup(_)
+            | Mode::Memory
+            | Mode::MemorySeries
+            | Mode::Idle
+            | Mode::Windows
+            | Mode::Eval
+            | Mode::Rendering
+            | Mode::Control => "index.html",
+        }
+    }
+}
+
+/// Event that counts as "ready" in startup mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ready {
+    /// The main window finished loading its page.
+    Load,
+    /// The page reported its first paint through `paint_report`.
+    Paint,
+    /// Fixed delay after setup() returns.
+    Timer,
+}
+
+impl Ready {
+    pub fn from_env() -> Self {
+        match env::var("BENCHMARK_READY").unwrap_or_default().as_str() {
+            "paint" => Ready::Paint,
+            "timer" => Ready::Timer,
+            _ => Ready::Load,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Ready::Load => "load",
+            Ready::Paint => "paint",
+            Ready::Timer => "timer",
+        }
+    }
+}
+
+/// Tunables handed to the frontend driver through the `bench_config` command.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchConfig {
+    pub iterations: u64,
+    pub warmup: u64,
+    pub settle_ms: u64,
+    pub duration_ms: u64,
+    pub interval_ms: u64,
+    pub payload_sizes: Vec<u64>,
+    pub payload_iterations: u64,
+    pub event_rate: u64,
+    pub stream_chunks: u64,
+    pub stream_chunk_bytes: u64,
+    pub asset_count: u64,
+    pub asset_sizes: Vec<u64>,
+    /// Sizes with an embedded fixture to compare against (see build.rs).
+    pub embedded_asset_sizes: Vec<u64>,
+    pub windows: u64,
+    pub window_op_iterations: u64,
+    pub eval_sizes: Vec<u64>,
+    pub eval_iterations: u64,
+    /// 0 leaves the main-thread probe off.
+    pub probe_interval_ms: u64,
+    pub scenario_file: Option<String>,
+    /// Phase deadlines for the watchdog; 0 turns one off.
+    pub deadline_window_ms: u64,
+    pub deadline_page_load_ms: u64,
+    pub deadline_scenario_ms: u64,
+}
+
+impl BenchConfig {
+    pub fn from_env() -> Self {
+        BenchConfig {
+            iterations: env_u64("BENCHMARK_ITERATIONS", 1000),
+            warmup: env_u64("BENCHMARK_WARMUP", 50),
+            settle_ms: env_u64("BENCHMARK_SETTLE_MS", 0),
+            duration_ms: env_u64("BENCHMARK_DURATION_MS", 30_000),
+            interval_ms: env_u64("BENCHMARK_INTERVAL_MS", 250).max(1),
+            payload_sizes: env_list_u64(
+                "BENCHMARK_PAYLOAD_SIZES",
+                &[1 << 10, 10 << 10, 100 << 10, 1 << 20, 10 << 20],
+            ),
+            payload_iterations: env_u64("BENCHMARK_PAYLOAD_ITERATIONS", 20),
+            event_rate: env_u64("BENCHMARK_EVENT_RATE", 1000),
+            stream_chunks: env_u64("BENCHMARK_STREAM_CHUNKS", 1000),
+            stream_chunk_bytes: env_u64("BENCHMARK_STREAM_CHUNK_BYTES", 1024),
+            asset_count: env_u64("BENCHMARK_ASSET_COUNT", 50),
+            asset_sizes: env_list_u64(
+                "BENCHMARK_ASSET_SIZES",
+                &[1 << 10, 10 << 10, 100 << 10, 1 << 20],
+            ),
+            embedded_asset_sizes: env!("ASSET_FIXTURES")
+                .split(',')
+                .filter_map(|n| n.parse().ok())
+                .collect(),
+            windows: env_u64("BENCHMARK_WINDOWS", 10),
+            window_op_iterations: env_u64("BENCHMARK_WINDOW_OP_ITERATIONS", 50),
+            eval_sizes: env_list_u64(
+                "BENCHMARK_EVAL_SIZES",
+                &[100, 1 << 10, 10 << 10, 100 << 10, 1 << 20],
+            ),
+            eval_iterations: env_u64("BENCHMARK_EVAL_ITERATIONS", 100),
+            probe_interval_ms: env_u64("BENCHMARK_PROBE_INTERVAL_MS", 0),
+            scenario_file: env::var("BENCHMARK_SCENARIO").ok(),
+            deadline_window_ms: env_u64("BENCHMARK_DEADLINE_WINDOW_MS", 10_000),
+            deadline_page_load_ms: env_u64("BENCHMARK_DEADLINE_PAGE_LOAD_MS", 10_000),
+            deadline_scenario_ms: env_u64("BENCHMARK_DEADLINE_SCENARIO_MS", 600_000),
+        }
+    }
+}
+
+/// Read a numeric setting from the environment, falling back to `default`
+/// when it is unset or unparsable.
+pub fn env_u64(name: &str, default: u64) -> u64 {
+    env::var(name)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(default)
+}
+
+/// Read a comma-separated list of numbers from the environment, falling back
+/// to `default` when it is unset or any entry is unparsable.
+pub fn env_list_u64(name: &str, default: &[u64]) -> Vec<u64> {
+    env::var(name)
+        .ok()
+        .and_then(|v| v.split(',').map(|n| n.trim().parse().ok()).collect())
+        .unwrap_or_else(|| default.to_vec())
+}
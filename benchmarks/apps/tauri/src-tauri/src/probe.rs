@@ -0,0 +1,84 @@
+// Main-thread responsiveness probe (BENCHMARK_PROBE_INTERVAL_MS).
+//
+// Commands, window operations and webview callbacks all queue up on the main
+// event loop, so a scenario can look fine on throughput while starving it.
+// With the variable set, a background thread hands the main thread a tiny
+// task through `run_on_main_thread` every BENCHMARK_PROBE_INTERVAL_MS from
+// the moment the page loads, and each task records how long it waited to
+// run. It works alongside any mode (an IPC flood with BENCHMARK=ipc, a long
+// event stream with BENCHMARK=events, ...); when the scenario finishes the
+// delay distribution is reported as the "main_thread" section, along with
+// how many tasks were still queued at that point.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::histogram::Histogram;
+use crate::stats::Summary;
+
+/// Probe state shared with the scheduling thread; kept in Tauri state.
+#[derive(Default)]
+pub struct Probe {
+    running: Arc<AtomicBool>,
+    scheduled: Arc<AtomicU64>,
+    /// Scheduling delay of every task that has run, in milliseconds.
+    delays: Arc<Mutex<Vec<f64>>>,
+    interval_ms: AtomicU64,
+}
+
+/// Start probing every `interval_ms`. Does nothing if already running.
+pub fn start<R: Runtime>(app: &AppHandle<R>, interval_ms: u64) {
+    let probe = app.state::<Probe>();
+    if probe.running.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    probe.interval_ms.store(interval_ms, Ordering::SeqCst);
+
+    let app = app.clone();
+    let running = probe.running.clone();
+    let scheduled = probe.scheduled.clone();
+    let delays = probe.delays.clone();
+    let period = Duration::from_millis(interval_ms.max(1));
+    std::thread::spawn(move || {
+        let start = Instant::now();
+        let mut seq = 0u32;
+        while running.load(Ordering::SeqCst) {
+            let due = start + period * seq;
+            std::thread::sleep(due.saturating_duration_since(Instant::now()));
+            let delays = delays.clone();
+            let sent = Instant::now();
+            let queued = app.run_on_main_thread(move || {
+                let delay = sent.elapsed().as_secs_f64() * 1e3;
+                delays.lock().unwrap().push(delay);
+            });
+            if queued.is_err() {
+                break;
+            }
+            scheduled.fetch_add(1, Ordering::SeqCst);
+            seq += 1;
+        }
+    });
+}
+
+/// Stop the probe and summarize it; `None` if it never ran.
+pub fn stop<R: Runtime>(app: &AppHandle<R>) -> Option<Value> {
+    let probe = app.try_state::<Probe>()?;
+    if !probe.running.swap(false, Ordering::SeqCst) {
+        return None;
+    }
+    let scheduled = probe.scheduled.load(Ordering::SeqCst);
+    let delays = probe.delays.lock().unwrap().clone();
+    Some(json!({
+        "interval_ms": probe.interval_ms.load(Ordering::SeqCst),
+        "scheduled": scheduled,
+        "ran": delays.len(),
+        // Queued but not yet run when the scenario ended.
+        "pending": scheduled.saturating_sub(delays.len() as u64),
+        "delay_ms": Summary::from_samples(&delays),
+        "delay_histogram": Histogram::from_samples(&delays).report(),
+    }))
+}
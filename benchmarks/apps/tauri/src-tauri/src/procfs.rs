@@ -0,0 +1,76 @@
+// Minimal /proc readers (Linux only).
+
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+
+/// Fields of /proc/<pid>/stat starting at field 3 (`state`), so field N of
+/// proc(5) is at index N - 3. `comm` (field 2) may contain spaces and
+/// parentheses, so the split starts after the last ')'.
+fn stat_fields(stat: &str) -> Option<Vec<&str>> {
+    let rest = &stat[stat.rfind(')')? + 1..];
+    Some(rest.split_whitespace().collect())
+}
+
+/// Read field `n` (1-based, as numbered in proc(5)) of /proc/<pid>/stat.
+pub fn stat_field(pid: &str, n: usize) -> Option<u64> {
+    let stat = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    stat_fields(&stat)?.get(n.checked_sub(3)?)?.parse().ok()
+}
+
+/// Process name from /proc/<pid>/comm.
+pub fn comm(pid: u32) -> String {
+    fs::read_to_string(format!("/proc/{pid}/comm"))
+        .map(|s| s.trim_end().to_owned())
+        .unwrap_or_default()
+}
+
+/// Thread ids of `pid`, from /proc/<pid>/task.
+pub fn tasks(pid: u32) -> Vec<u32> {
+    fs::read_dir(format!("/proc/{pid}/task"))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// All descendants of `root`, breadth first (children before grandchildren).
+pub fn descendants(root: u32) -> Vec<u32> {
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    if let Ok(entries) = fs::read_dir("/proc") {
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let Some(pid) = name.to_str().and_then(|s| s.parse::<u32>().ok()) else {
+                continue;
+            };
+            // Field 4 is the parent pid.
+            if let Some(ppid) = stat_field(&pid.to_string(), 4) {
+                children.entry(ppid as u32).or_default().push(pid);
+            }
+        }
+    }
+
+    let mut found = Vec::new();
+    let mut queue = VecDeque::from([root]);
+    while let Some(pid) = queue.pop_front() {
+        if let Some(kids) = children.get_mut(&pid) {
+            kids.sort_unstable();
+            found.extend(kids.iter().copied());
+            queue.extend(kids.iter().copied());
+        }
+    }
+    found
+}
+
+/// Numeric `key: value [kB]` pairs from a smaps_rollup/status style file.
+pub fn kb_fields(text: &str) -> HashMap<&str, u64> {
+    text.lines()
+        .filter_map(|line| {
+            let (key, rest) = line.split_once(':')?;
+            let value = rest.split_whitespace().next()?.parse().ok()?;
+            Some((key, value))
+        })
+        .collect()
+}
@@ -0,0 +1,167 @@
+// Rendering configuration (Linux CI) and forced software rendering.
+//
+// Linux results depend heavily on how WebKitGTK renders: a GPU box with
+// accelerated compositing and a headless CI runner under Xvfb with llvmpipe
+// are not comparable. Every run therefore records its rendering setup, as a
+// "rendering" section in the JSON report (BENCHMARK=rendering prints it on
+// its own and exits):
+//
+//   platform                        std::env::consts::OS
+//   webkitgtk                       runtime WebKitGTK version
+//   hardware_acceleration_policy    the webview's policy: on-demand, always
+//                                   or never
+//   compositing                     "disabled" when
+//                                   WEBKIT_DISABLE_COMPOSITING_MODE is set,
+//                                   "policy" otherwise
+//   display_backend                 the GDK display: x11, wayland, ...
+//   display                         $WAYLAND_DISPLAY or $DISPLAY
+//   software                        whether BENCHMARK_RENDERING=software
+//                                   forced software rendering
+//   env                             the rendering-related variables as set
+//
+// BENCHMARK_RENDERING=software makes runs reproducible without a GPU: before
+// GTK starts it sets WEBKIT_DISABLE_COMPOSITING_MODE,
+// WEBKIT_DISABLE_DMABUF_RENDERER and LIBGL_ALWAYS_SOFTWARE, and the main
+// webview's hardware acceleration policy is set to never. Elsewhere it is
+// accepted and recorded but changes nothing.
+
+use std::env;
+#[cfg(target_os = "linux")]
+use std::sync::{Arc, Mutex};
+
+use serde_json::{Map, Value};
+use tauri::{Runtime, WebviewWindow};
+
+/// Variables that change how WebKitGTK renders, reported as found.
+const RENDERING_ENV: &[&str] = &[
+    "WEBKIT_DISABLE_COMPOSITING_MODE",
+    "WEBKIT_DISABLE_DMABUF_RENDERER",
+    "WEBKIT_FORCE_COMPOSITING_MODE",
+    "LIBGL_ALWAYS_SOFTWARE",
+    "GALLIUM_DRIVER",
+    "GDK_BACKEND",
+];
+
+/// Set by BENCHMARK_RENDERING=software.
+#[cfg(target_os = "linux")]
+const SOFTWARE_ENV: &[(&str, &str)] = &[
+    ("WEBKIT_DISABLE_COMPOSITING_MODE", "1"),
+    ("WEBKIT_DISABLE_DMABUF_RENDERER", "1"),
+    ("LIBGL_ALWAYS_SOFTWARE", "1"),
+];
+
+pub fn software_requested() -> bool {
+    env::var("BENCHMARK_RENDERING").is_ok_and(|v| v == "software")
+}
+
+/// Export the software-rendering variables. Must run first thing in `main`,
+/// before GTK or WebKit read them.
+pub fn init() {
+    #[cfg(target_os = "linux")]
+    if software_requested() {
+        for (name, value) in SOFTWARE_ENV {
+            env::set_var(name, value);
+        }
+    }
+}
+
+/// Apply per-webview settings to a freshly built window.
+pub fn apply<R: Runtime>(window: &WebviewWindow<R>) {
+    #[cfg(target_os = "linux")]
+    if software_requested() {
+        let _ = window.with_webview(|webview| {
+            use webkit2gtk::{HardwareAccelerationPolicy, SettingsExt, WebViewExt};
+            if let Some(settings) = webview.inner().settings() {
+                settings.set_hardware_acceleration_policy(HardwareAccelerationPolicy::Never);
+            }
+        });
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = window;
+}
+
+/// Describe how `window` renders and hand the result to `done`, which runs
+/// on the main thread. If the webview can't be reached, `done` runs right
+/// away with what is known without it.
+pub fn describe<R: Runtime>(window: &WebviewWindow<R>, done: impl FnOnce(Value) + Send + 'static) {
+    let mut info = Map::new();
+    info.insert("platform".into(), env::consts::OS.into());
+    info.insert("software".into(), software_requested().into());
+    info.insert(
+        "env".into(),
+        RENDERING_ENV
+            .iter()
+            .map(|name| (name.to_string(), env::var(name).ok().into()))
+            .collect::<Map<_, _>>()
+            .into(),
+    );
+
+    #[cfg(target_os = "linux")]
+    {
+        let display = env::var("WAYLAND_DISPLAY")
+            .or_else(|_| env::var("DISPLAY"))
+            .ok();
+        info.insert("display".into(), display.into());
+        info.insert(
+            "compositing".into(),
+            if env::var_os("WEBKIT_DISABLE_COMPOSITING_MODE").is_some() {
+                "disabled"
+            } else {
+                "policy"
+            }
+            .into(),
+        );
+        let done = Arc::new(Mutex::new(Some(done)));
+        let fallback = (Arc::clone(&done), info.clone());
+        let reached = window.with_webview(move |webview| {
+            use gtk::prelude::*;
+            use webkit2gtk::{HardwareAccelerationPolicy, SettingsExt, WebViewExt};
+
+            // SAFETY: plain getters with no preconditions.
+            let version = unsafe {
+                use webkit2gtk::ffi;
+                format!(
+                    "{}.{}.{}",
+                    ffi::webkit_get_major_version(),
+                    ffi::webkit_get_minor_version(),
+                    ffi::webkit_get_micro_version()
+                )
+            };
+            info.insert("webkitgtk".into(), version.into());
+
+            let policy = WebViewExt::settings(&webview.inner()).map(|settings| {
+                match settings.hardware_acceleration_policy() {
+                    HardwareAccelerationPolicy::OnDemand => "on-demand".to_owned(),
+                    HardwareAccelerationPolicy::Always => "always".to_owned(),
+                    HardwareAccelerationPolicy::Never => "never".to_owned(),
+                    other => format!("{other:?}"),
+                }
+            });
+            info.insert("hardware_acceleration_policy".into(), policy.into());
+
+            let backend =
+                gtk::gdk::Display::default().map(|display| match display.type_().name() {
+                    "GdkX11Display" => "x11".to_owned(),
+                    "GdkWaylandDisplay" => "wayland".to_owned(),
+                    other => other.to_owned(),
+                });
+            info.insert("display_backend".into(), backend.into());
+            if let Some(done) = done.lock().unwrap().take() {
+                done(Value::Object(info));
+            }
+        });
+        if reached.is_err() {
+            let (done, info) = fallback;
+            let done = done.lock().unwrap().take();
+            if let Some(done) = done {
+                done(Value::Object(info));
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = window;
+        done(Value::Object(info));
+    }
+}
@@ -0,0 +1,299 @@
+// Structured benchmark report (BENCHMARK_REPORT=json).
+//
+// Without the variable the app keeps its historical output: scenario results
+// as individual JSON lines followed by "ready". With it, every phase
+// timestamp and scenario result is collected here and printed as a single
+// JSON document from the RunEvent::Exit handler, after "ready", so teardown
+// is covered too:
+//
+//   {
+//     "schema_version": 1,
+//     "mode": "startup",
+//     "ready": "load",
+//     "clock": "boottime",
+//     "phases": { "process_start": .., "main": .., "builder": .., "setup": ..,
+//                 "window_created": .., "page_loaded": .., "exit_requested": ..,
+//                 "exit": .. },
+//     "shutdown": { "startup_ms": .., "shutdown_ms": .. },
+//     "rendering": { "webkitgtk": .., ... },          (see rendering.rs)
+//     "error": { "kind": .., "exit_code": .., ... },  (failed runs, error.rs)
+//     ...scenario sections ("paint", "ipc", ...)
+//   }
+//
+// Phase values are milliseconds on `clock` (see timing.rs); a phase that was
+// never reached is null. `startup_ms` runs from process start (or `main` where
+// the OS doesn't expose it) to `exit_requested`, `shutdown_ms` from there to
+// `exit`. Whatever the runtime does after RunEvent::Exit is not visible
+// in-process and remains part of the harness's wall-clock figure.
+
+use std::env;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Mutex;
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::error::Error;
+use crate::mode::Mode;
+use crate::probe;
+use crate::timing;
+
+/// Bumped whenever a field is renamed or removed.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// What the harness waits for on stdout before it stops the clock.
+pub const READY_LINE: &str = "ready";
+
+/// Startup milestones, in the order they happen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Main,
+    Builder,
+    Setup,
+    WindowCreated,
+    PageLoaded,
+    ExitRequested,
+    Exit,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct Phases {
+    pub process_start: Option<f64>,
+    pub main: Option<f64>,
+    pub builder: Option<f64>,
+    pub setup: Option<f64>,
+    pub window_created: Option<f64>,
+    pub page_loaded: Option<f64>,
+    pub exit_requested: Option<f64>,
+    pub exit: Option<f64>,
+}
+
+impl Phases {
+    fn slot(&mut self, phase: Phase) -> &mut Option<f64> {
+        match phase {
+            Phase::Main => &mut self.main,
+            Phase::Builder => &mut self.builder,
+            Phase::Setup => &mut self.setup,
+            Phase::WindowCreated => &mut self.window_created,
+            Phase::PageLoaded => &mut self.page_loaded,
+            Phase::ExitRequested => &mut self.exit_requested,
+            Phase::Exit => &mut self.exit,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Document<'a> {
+    schema_version: u32,
+    mode: &'static str,
+    ready: Option<&'static str>,
+    clock: &'static str,
+    phases: &'a Phases,
+    #[serde(flatten)]
+    sections: &'a Map<String, Value>,
+}
+
+/// What a redirected report passes on instead of printing (see
+/// [`Report::redirect`]).
+pub enum Outcome {
+    Section(String, Value),
+    Finished,
+    Failed(Error),
+}
+
+/// Collects phase timestamps and scenario results; kept in Tauri state.
+pub struct Report {
+    json: bool,
+    mode: Mode,
+    phases: Mutex<Phases>,
+    sections: Mutex<Map<String, Value>>,
+    sink: Mutex<Option<Sender<Outcome>>>,
+    exit_code: AtomicI32,
+}
+
+impl Report {
+    pub fn new(mode: Mode) -> Self {
+        Self::with_json(
+            mode,
+            env::var("BENCHMARK_REPORT").is_ok_and(|v| v == "json"),
+        )
+    }
+
+    /// A report for `mode`, in JSON-report mode or not regardless of
+    /// BENCHMARK_REPORT.
+    pub fn with_json(mode: Mode, json: bool) -> Self {
+        Report {
+            json,
+            mode,
+            phases: Mutex::new(Phases {
+                process_start: timing::process_start_ms(),
+                ..Phases::default()
+            }),
+            sections: Mutex::new(Map::new()),
+            sink: Mutex::new(None),
+            exit_code: AtomicI32::new(0),
+        }
+    }
+
+    /// Send scenario results and the end of the scenario to `sink` instead
+    /// of reporting them and exiting, until called again with `None`. Used
+    /// by the control channel to run scenarios in a live instance.
+    pub fn redirect(&self, sink: Option<Sender<Outcome>>) {
+        *self.sink.lock().unwrap() = sink;
+    }
+
+    /// Record `phase` as reached now. Only the first occurrence counts, so a
+    /// later navigation can't move `page_loaded`; returns whether this call
+    /// was it.
+    pub fn mark(&self, phase: Phase) -> bool {
+        let now = timing::now_ms();
+        let mut phases = self.phases.lock().unwrap();
+        let slot = phases.slot(phase);
+        let first = slot.is_none();
+        slot.get_or_insert(now);
+        first
+    }
+
+    /// The phases reached so far.
+    pub fn phases(&self) -> Phases {
+        self.phases.lock().unwrap().clone()
+    }
+
+    /// Publish a scenario result: passed to the sink while redirected,
+    /// stored under `name` in JSON-report mode, printed immediately as its
+    /// own line otherwise.
+    pub fn emit(&self, name: &str, value: Value) {
+        if let Some(sink) = self.sink.lock().unwrap().as_ref() {
+            let _ = sink.send(Outcome::Section(name.to_owned(), value));
+        } else if self.json {
+            self.sections.lock().unwrap().insert(name.to_owned(), value);
+        } else {
+            println!("{value}");
+        }
+    }
+
+    /// Whether this is a JSON-report run (BENCHMARK_REPORT=json).
+    pub fn is_json(&self) -> bool {
+        self.json
+    }
+
+    /// Whether `emit` prints each result as its own line right away (the
+    /// legacy output), so a mode may print intermediate lines too.
+    pub fn prints_lines(&self) -> bool {
+        !self.json && self.sink.lock().unwrap().is_none()
+    }
+
+    /// Attach context about the run (not a scenario result): stored under
+    /// `name` in JSON-report mode, dropped otherwise so the legacy output
+    /// stays as it was.
+    pub fn annotate(&self, name: &str, value: Value) {
+        if self.json {
+            self.sections.lock().unwrap().insert(name.to_owned(), value);
+        }
+    }
+
+    /// The end of a run: writes the ready line to `out` and returns the
+    /// exit code. While redirected, the sink is told instead and `None`
+    /// means the app keeps running.
+    pub fn finished(&self, out: &mut impl Write) -> Option<i32> {
+        if let Some(sink) = self.sink.lock().unwrap().as_ref() {
+            let _ = sink.send(Outcome::Finished);
+            return None;
+        }
+        let _ = writeln!(out, "{READY_LINE}");
+        Some(0)
+    }
+
+    /// A run that failed: writes the error record to `out`, adds it to the
+    /// report and returns the exit code. While redirected, the error goes to
+    /// the sink instead and `None` means the app keeps running.
+    pub fn failed(&self, error: Error, out: &mut impl Write) -> Option<i32> {
+        if let Some(sink) = self.sink.lock().unwrap().as_ref() {
+            let _ = sink.send(Outcome::Failed(error));
+            return None;
+        }
+        let record = error.to_json();
+        let _ = writeln!(out, "{record}");
+        self.annotate("error", record);
+        self.exit_code.store(error.exit_code(), Ordering::Relaxed);
+        Some(error.exit_code())
+    }
+
+    /// The code the process should exit with: the failed run's, 0 otherwise.
+    pub fn exit_code(&self) -> i32 {
+        self.exit_code.load(Ordering::Relaxed)
+    }
+
+    /// RunEvent::Exit: record the end of teardown and, in JSON-report mode,
+    /// add the startup/shutdown split and return the finished document.
+    pub fn exited(&self) -> Option<Value> {
+        self.mark(Phase::Exit);
+        if !self.json {
+            return None;
+        }
+
+        let shutdown = {
+            let phases = self.phases.lock().unwrap();
+            let origin = phases.process_start.or(phases.main);
+            let requested = phases.exit_requested;
+            json!({
+                "startup_ms": origin.zip(requested).map(|(a, b)| b - a),
+                "shutdown_ms": requested.zip(phases.exit).map(|(a, b)| b - a),
+            })
+        };
+        self.emit("shutdown", shutdown);
+        Some(self.to_json())
+    }
+
+    pub fn to_json(&self) -> Value {
+        let phases = self.phases.lock().unwrap();
+        let sections = self.sections.lock().unwrap();
+        serde_json::to_value(Document {
+            schema_version: SCHEMA_VERSION,
+            mode: self.mode.name(),
+            ready: match self.mode {
+                Mode::Startup(ready) => Some(ready.name()),
+                _ => None,
+            },
+            clock: timing::CLOCK,
+            phases: &phases,
+            sections: &sections,
+        })
+        .unwrap_or(Value::Null)
+    }
+}
+
+/// End of a benchmark run: wrap up the main-thread probe if it was running,
+/// print "ready" and ask the app to exit. The report itself is written by
+/// [`exited`] once teardown has run. While redirected, the sink is told
+/// instead and the app keeps running.
+pub fn finish<R: Runtime>(app: &AppHandle<R>) {
+    let report = app.state::<Report>();
+    if let Some(main_thread) = probe::stop(app) {
+        report.emit("main_thread", main_thread);
+    }
+    if let Some(code) = report.finished(&mut io::stdout()) {
+        app.exit(code);
+    }
+}
+
+/// End a benchmark run early: the error record goes to stderr instead of
+/// "ready", and the app exits with the error's code (see error.rs). While
+/// redirected, the sink gets the error instead.
+pub fn fail<R: Runtime>(app: &AppHandle<R>, error: Error) {
+    let report = app.state::<Report>();
+    if let Some(code) = report.failed(error, &mut io::stderr()) {
+        app.exit(code);
+    }
+}
+
+/// RunEvent::Exit: record the end of teardown and, in JSON-report mode,
+/// print the document with the startup/shutdown split.
+pub fn exited<R: Runtime>(app: &AppHandle<R>) {
+    if let Some(document) = app.state::<Report>().exited() {
+        println!("{document}");
+    }
+}
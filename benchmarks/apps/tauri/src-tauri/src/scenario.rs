@@ -0,0 +1,346 @@
+// Declarative scenarios (BENCHMARK=scenario).
+//
+// BENCHMARK_SCENARIO names a JSON file listing steps to run in order once
+// scenario.html has loaded in the main window, so a new comparison is a new
+// file rather than a new mode:
+//
+//   {
+//     "name": "windows-then-ipc",
+//     "steps": [
+//       { "step": "wait_for_paint" },
+//       { "step": "open_window", "page": "index.html" },
+//       { "step": "invoke", "command": "payload_string", "iterations": 100,
+//         "payload_bytes": 10240 },
+//       { "step": "emit_events", "count": 1000, "rate_hz": 500 },
+//       { "step": "sleep", "ms": 1000 },
+//       { "step": "sample_memory" }
+//     ]
+//   }
+//
+// Steps:
+//
+//   open_window     open a 400x300 window on `page` (default index.html);
+//                   creation and page-load time
+//   invoke          call `command` `iterations` times (default 100) from the
+//                   page; `args` is passed as given, or `payload_bytes` puts a
+//                   string of that length under `arg` (default "data")
+//   wait_for_paint  wait for the main window's next painted frame
+//   sample_memory   RSS/PSS/USS of the process tree
+//   emit_events     emit `count` events at `rate_hz` (default 1000) to the
+//                   page; delivery count and one-way latency
+//   sleep           wait `ms`
+//
+// Page-side work is done by ../src/bench/scenario.js: each request is evaled
+// into the page and answered through `scenario_reply`. Every step's result,
+// with its wall time, goes into one "scenario" section, then the app exits.
+// An unreadable file ends the run as a config error, a step that fails as a
+// scenario error, or a timeout if it didn't finish in time (see error.rs for
+// the exit codes); the steps up to and including the failed one are still
+// reported.
+
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use tauri::webview::PageLoadEvent;
+use tauri::{AppHandle, Emitter, Manager, State, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+
+use crate::error::Error;
+use crate::histogram::Histogram;
+use crate::memory;
+use crate::mode::BenchConfig;
+use crate::report::{self, Report};
+use crate::stats::Summary;
+use crate::timing;
+
+/// How long a page-side step, or a window's page load, may take.
+const STEP_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Deserialize)]
+pub struct Scenario {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub steps: Vec<Step>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "step", rename_all = "snake_case")]
+pub enum Step {
+    OpenWindow {
+        #[serde(default)]
+        label: Option<String>,
+        #[serde(default = "default_page")]
+        page: String,
+    },
+    Invoke {
+        command: String,
+        #[serde(default = "default_iterations")]
+        iterations: u64,
+        #[serde(default)]
+        args: Option<Map<String, Value>>,
+        #[serde(default)]
+        payload_bytes: Option<usize>,
+        #[serde(default = "default_arg")]
+        arg: String,
+    },
+    WaitForPaint,
+    SampleMemory,
+    EmitEvents {
+        count: u64,
+        #[serde(default = "default_rate")]
+        rate_hz: u64,
+    },
+    Sleep {
+        ms: u64,
+    },
+}
+
+fn default_page() -> String {
+    "index.html".to_owned()
+}
+
+fn default_iterations() -> u64 {
+    100
+}
+
+fn default_arg() -> String {
+    "data".to_owned()
+}
+
+fn default_rate() -> u64 {
+    1000
+}
+
+impl Step {
+    fn name(&self) -> &'static str {
+        match self {
+            Step::OpenWindow { .. } => "open_window",
+            Step::Invoke { .. } => "invoke",
+            Step::WaitForPaint => "wait_for_paint",
+            Step::SampleMemory => "sample_memory",
+            Step::EmitEvents { .. } => "emit_events",
+            Step::Sleep { .. } => "sleep",
+        }
+    }
+}
+
+/// Where `scenario_reply` forwards the page's answers; kept in Tauri state.
+#[derive(Default)]
+pub struct ScenarioReplies(Mutex<Option<Sender<(u64, Value)>>>);
+
+/// The page's answer to one request.
+#[tauri::command]
+pub fn scenario_reply(replies: State<'_, ScenarioReplies>, id: u64, result: Value) {
+    if let Some(tx) = replies.0.lock().unwrap().as_ref() {
+        let _ = tx.send((id, result));
+    }
+}
+
+struct Runner<'a> {
+    app: &'a AppHandle,
+    page: WebviewWindow,
+    replies: Receiver<(u64, Value)>,
+    next_id: u64,
+    windows: u64,
+}
+
+impl Runner<'_> {
+    /// Eval one request into the page and wait for its answer.
+    fn ask(&mut self, op: &str, params: Value) -> Result<Value, Error> {
+        self.next_id += 1;
+        let id = self.next_id;
+        let mut request = json!({ "id": id, "op": op });
+        if let (Some(request), Value::Object(params)) = (request.as_object_mut(), params) {
+            request.extend(params);
+        }
+        self.page
+            .eval(format!("window.__scenario.run({request})"))
+            .map_err(|e| Error::Webview(e.to_string()))?;
+
+        let deadline = Instant::now() + STEP_TIMEOUT;
+        loop {
+            let (reply_id, result) = self
+                .replies
+                .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                .map_err(|_| {
+                    Error::Timeout(format!("page did not answer {op} within {STEP_TIMEOUT:?}"))
+                })?;
+            if reply_id != id {
+                continue;
+            }
+            return match result.get("error") {
+                Some(error) => Err(Error::Scenario(format!("{op}: {error}"))),
+                None => Ok(result),
+            };
+        }
+    }
+
+    fn step(&mut self, step: &Step) -> Result<Value, Error> {
+        match step {
+            Step::OpenWindow { label, page } => {
+                self.windows += 1;
+                let label = label
+                    .clone()
+                    .unwrap_or_else(|| format!("scenario-{}", self.windows));
+                let (tx, rx) = mpsc::channel();
+                let start = Instant::now();
+                WebviewWindowBuilder::new(self.app, &label, WebviewUrl::App(page.into()))
+                    .title("Hello World")
+                    .inner_size(400.0, 300.0)
+                    .on_page_load(move |_, payload| {
+                        if payload.event() == PageLoadEvent::Finished {
+                            let _ = tx.send(Instant::now());
+                        }
+                    })
+                    .build()
+                    .map_err(|e| Error::Window(e.to_string()))?;
+                let create_ms = start.elapsed().as_secs_f64() * 1e3;
+                let loaded = rx.recv_timeout(STEP_TIMEOUT).map_err(|_| {
+                    Error::Timeout(format!("{page} did not load within {STEP_TIMEOUT:?}"))
+                })?;
+                Ok(json!({
+                    "label": label,
+                    "create_ms": create_ms,
+                    "page_load_ms": loaded.duration_since(start).as_secs_f64() * 1e3,
+                }))
+            }
+            Step::Invoke {
+                command,
+                iterations,
+                args,
+                payload_bytes,
+                arg,
+            } => {
+                let mut args = args.clone().unwrap_or_default();
+                if let Some(bytes) = payload_bytes {
+                    args.insert(arg.clone(), Value::String("x".repeat(*bytes)));
+                }
+                let answer = self.ask(
+                    "invoke",
+                    json!({ "command": command, "iterations": iterations, "args": args }),
+                )?;
+                let samples: Vec<f64> =
+                    serde_json::from_value(answer["samples"].clone()).unwrap_or_default();
+                let resolution_ms = answer["timerResolutionMs"].as_f64().unwrap_or(0.0);
+                Ok(json!({
+                    "command": command,
+                    "iterations": iterations,
+                    "latency_ms": Summary::from_samples(&samples),
+                    "latency_histogram": Histogram::from_samples_at(&samples, resolution_ms)
+                        .report(),
+                }))
+            }
+            Step::WaitForPaint => {
+                let answer = self.ask("paint", json!({}))?;
+                Ok(json!({ "paint_ms": answer["paintMs"] }))
+            }
+            Step::SampleMemory => serde_json::to_value(memory::sample_tree())
+                .map_err(|e| Error::Scenario(e.to_string())),
+            Step::EmitEvents { count, rate_hz } => {
+                let event = format!("bench:scenario-{}", self.next_id + 1);
+                self.ask("listen", json!({ "event": event }))?;
+                let period = Duration::from_secs_f64(1.0 / (*rate_hz).max(1) as f64);
+                let start = Instant::now();
+                for seq in 0..*count {
+                    let due = start + period.mul_f64(seq as f64);
+                    std::thread::sleep(due.saturating_duration_since(Instant::now()));
+                    let _ = self
+                        .app
+                        .emit(&event, json!({ "seq": seq, "sentAt": timing::epoch_ms() }));
+                }
+                let _ = self
+                    .app
+                    .emit(&format!("{event}:done"), json!({ "sent": count }));
+                let answer = self.ask("collect", json!({ "event": event }))?;
+                let latencies: Vec<f64> =
+                    serde_json::from_value(answer["latencies"].clone()).unwrap_or_default();
+                let resolution_ms = answer["timerResolutionMs"].as_f64().unwrap_or(0.0);
+                Ok(json!({
+                    "sent": count,
+                    "received": answer["received"],
+                    "rate_hz": rate_hz,
+                    "latency_ms": Summary::from_samples(&latencies),
+                    "latency_histogram": Histogram::from_samples_at(&latencies, resolution_ms)
+                        .report(),
+                }))
+            }
+            Step::Sleep { ms } => {
+                std::thread::sleep(Duration::from_millis(*ms));
+                Ok(json!({ "ms": ms }))
+            }
+        }
+    }
+}
+
+fn load(path: &str) -> Result<Scenario, Error> {
+    let text = std::fs::read_to_string(path).map_err(|e| Error::Config(format!("{path}: {e}")))?;
+    serde_json::from_str(&text).map_err(|e| Error::Config(format!("{path}: {e}")))
+}
+
+/// Run the scenario file against the loaded main window and report.
+/// Blocks, so call it off the main thread.
+pub fn run(app: &AppHandle, config: &BenchConfig) {
+    let Some(path) = config.scenario_file.as_deref() else {
+        let error = Error::Config("BENCHMARK=scenario needs BENCHMARK_SCENARIO=<file>".into());
+        return report::fail(app, error);
+    };
+    let scenario = match load(path) {
+        Ok(scenario) => scenario,
+        Err(error) => return report::fail(app, error),
+    };
+    let Some(page) = app.get_webview_window("main") else {
+        return report::fail(app, Error::Window("the main window is gone".into()));
+    };
+
+    let (tx, rx) = mpsc::channel();
+    *app.state::<ScenarioReplies>().0.lock().unwrap() = Some(tx);
+    let mut runner = Runner {
+        app,
+        page,
+        replies: rx,
+        next_id: 0,
+        windows: 0,
+    };
+
+    let mut steps = Vec::new();
+    let mut failed = None;
+    for (index, step) in scenario.steps.iter().enumerate() {
+        let start = Instant::now();
+        let result = runner.step(step);
+        let elapsed_ms = start.elapsed().as_secs_f64() * 1e3;
+        let (ok, result) = match result {
+            Ok(result) => (true, result),
+            Err(error) => {
+                let result = json!({ "error": error.message(), "kind": error.kind() });
+                failed = Some(error);
+                (false, result)
+            }
+        };
+        steps.push(json!({
+            "index": index,
+            "step": step.name(),
+            "ok": ok,
+            "elapsed_ms": elapsed_ms,
+            "result": result,
+        }));
+        if !ok {
+            break;
+        }
+    }
+
+    app.state::<Report>().emit(
+        "scenario",
+        json!({
+            "scenario": "scenario",
+            "name": scenario.name,
+            "file": path,
+            "steps": steps,
+        }),
+    );
+    match failed {
+        None => report::finish(app),
+        Some(error) => report::fail(app, error),
+    }
+}
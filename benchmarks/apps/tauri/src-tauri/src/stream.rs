@@ -0,0 +1,167 @@
+// Ordered streaming: tauri::ipc::Channel vs Emitter::emit (BENCHMARK=stream).
+//
+// Both transports carry the same BENCHMARK_STREAM_CHUNKS chunks of
+// BENCHMARK_STREAM_CHUNK_BYTES each, sent back to back from a background
+// thread. ../src/bench/stream.js drives one transport after the other and
+// records the sequence number and arrival time of every chunk; the results
+// come back through `stream_report`, which works out throughput and how many
+// chunks arrived out of order. Channel chunks are counted in the order the
+// page delivers them, which like @tauri-apps/api's Channel is by index, so
+// their out-of-order count is zero by construction; `held_back` says how
+// many had to wait for an earlier one to get there.
+//
+// A channel signals its end when the last clone is dropped; the emit stream
+// ends with a `bench:chunk-end` event carrying the number of chunks sent.
+
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::ipc::Channel;
+use tauri::{AppHandle, Emitter, Runtime, State};
+
+use crate::mode::BenchConfig;
+use crate::report::{self, Report};
+use crate::stats::Summary;
+
+const CHUNK: &str = "bench:chunk";
+const CHUNK_END: &str = "bench:chunk-end";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Chunk {
+    pub seq: u64,
+    pub data: String,
+}
+
+fn chunks(config: &BenchConfig) -> impl Iterator<Item = Chunk> {
+    let data = "x".repeat(config.stream_chunk_bytes as usize);
+    (0..config.stream_chunks).map(move |seq| Chunk {
+        seq,
+        data: data.clone(),
+    })
+}
+
+/// Stream every chunk through the page's channel, then drop it to end it.
+#[tauri::command]
+pub fn stream_channel(config: State<'_, BenchConfig>, channel: Channel<Chunk>) {
+    let config = config.inner().clone();
+    std::thread::spawn(move || {
+        for chunk in chunks(&config) {
+            if channel.send(chunk).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+/// Stream every chunk as a `bench:chunk` event, then `bench:chunk-end`.
+#[tauri::command]
+pub fn stream_emit<R: Runtime>(app: AppHandle<R>, config: State<'_, BenchConfig>) {
+    let config = config.inner().clone();
+    std::thread::spawn(move || {
+        let started = Instant::now();
+        let mut sent = 0u64;
+        for chunk in chunks(&config) {
+            if app.emit(CHUNK, chunk).is_ok() {
+                sent += 1;
+            }
+        }
+        let _ = app.emit(
+            CHUNK_END,
+            json!({ "sent": sent, "elapsedMs": started.elapsed().as_secs_f64() * 1e3 }),
+        );
+    });
+}
+
+/// One transport's run as seen by the page.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamSamples {
+    pub transport: String,
+    pub sent: u64,
+    /// `performance.now()` just before the start command was invoked.
+    pub started_at: f64,
+    /// Sequence numbers in arrival order.
+    pub seqs: Vec<u64>,
+    /// `performance.now()` at each arrival.
+    pub arrivals: Vec<f64>,
+    /// Chunks that arrived before an earlier one and were held back to keep
+    /// the stream in order (channel only).
+    #[serde(default)]
+    pub held_back: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StreamStats {
+    pub transport: String,
+    pub chunk_bytes: u64,
+    pub sent: u64,
+    pub received: usize,
+    /// Chunks that arrived after one with a higher sequence number.
+    pub out_of_order: usize,
+    /// Chunks the page held back until the ones before them arrived.
+    pub held_back: u64,
+    /// From invoking the start command to the last arrival.
+    pub duration_ms: Option<f64>,
+    pub chunks_per_s: Option<f64>,
+    pub throughput_mb_s: Option<f64>,
+    pub inter_arrival_ms: Option<Summary>,
+}
+
+impl StreamStats {
+    pub fn from_samples(chunk_bytes: u64, samples: StreamSamples) -> Self {
+        let received = samples.seqs.len();
+        let mut highest = None;
+        let mut out_of_order = 0;
+        for &seq in &samples.seqs {
+            if highest.is_some_and(|h| seq < h) {
+                out_of_order += 1;
+            }
+            highest = highest.max(Some(seq));
+        }
+        let duration_ms = samples
+            .arrivals
+            .last()
+            .map(|last| last - samples.started_at)
+            .filter(|ms| *ms > 0.0);
+        let per_second = |n: f64| duration_ms.map(|ms| n / (ms / 1e3));
+        let intervals: Vec<f64> = samples.arrivals.windows(2).map(|w| w[1] - w[0]).collect();
+
+        StreamStats {
+            transport: samples.transport,
+            chunk_bytes,
+            sent: samples.sent,
+            received,
+            out_of_order,
+            held_back: samples.held_back,
+            duration_ms,
+            chunks_per_s: per_second(received as f64),
+            throughput_mb_s: per_second(received as f64 * chunk_bytes as f64 / 1e6),
+            inter_arrival_ms: Summary::from_samples(&intervals),
+        }
+    }
+}
+
+/// Final call from the stream driver: report both transports and quit.
+#[tauri::command]
+pub fn stream_report<R: Runtime>(
+    app: AppHandle<R>,
+    config: State<'_, BenchConfig>,
+    report: State<'_, Report>,
+    results: Vec<StreamSamples>,
+) {
+    let results: Vec<StreamStats> = results
+        .into_iter()
+        .map(|r| StreamStats::from_samples(config.stream_chunk_bytes, r))
+        .collect();
+    report.emit(
+        "stream",
+        json!({
+            "scenario": "stream",
+            "chunks": config.stream_chunks,
+            "chunk_bytes": config.stream_chunk_bytes,
+            "results": results,
+        }),
+    );
+    report::finish(&app);
+}
@@ -0,0 +1,66 @@
+// Monotonic timestamps for the startup-phase report.
+//
+// All values are milliseconds on one clock so they can be subtracted from
+// each other. On Linux that clock is CLOCK_BOOTTIME, which is also the base
+// of the `starttime` field in /proc/self/stat, so the moment the kernel
+// created the process lands on the same axis as everything we record later.
+
+/// Name of the clock behind [`now_ms`], reported alongside the timestamps.
+#[cfg(target_os = "linux")]
+pub const CLOCK: &str = "boottime";
+#[cfg(all(unix, not(target_os = "linux")))]
+pub const CLOCK: &str = "monotonic";
+#[cfg(not(unix))]
+pub const CLOCK: &str = "instant";
+
+/// Current time in milliseconds on [`CLOCK`].
+#[cfg(unix)]
+pub fn now_ms() -> f64 {
+    #[cfg(target_os = "linux")]
+    const CLOCK_ID: libc::clockid_t = libc::CLOCK_BOOTTIME;
+    #[cfg(not(target_os = "linux"))]
+    const CLOCK_ID: libc::clockid_t = libc::CLOCK_MONOTONIC;
+
+    let mut ts = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // SAFETY: `ts` is a valid, writable timespec and CLOCK_ID is supported
+    // on every platform this branch compiles for.
+    unsafe { libc::clock_gettime(CLOCK_ID, &mut ts) };
+    ts.tv_sec as f64 * 1e3 + ts.tv_nsec as f64 / 1e6
+}
+
+/// Current time in milliseconds since the first call (no absolute clock).
+#[cfg(not(unix))]
+pub fn now_ms() -> f64 {
+    use std::sync::OnceLock;
+    use std::time::Instant;
+
+    static ANCHOR: OnceLock<Instant> = OnceLock::new();
+    ANCHOR.get_or_init(Instant::now).elapsed().as_secs_f64() * 1e3
+}
+
+/// Wall-clock time in milliseconds since the Unix epoch, comparable with
+/// `performance.timeOrigin + performance.now()` in the webview.
+pub fn epoch_ms() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs_f64() * 1e3)
+        .unwrap_or(0.0)
+}
+
+/// When the kernel started this process, in milliseconds on [`CLOCK`].
+#[cfg(target_os = "linux")]
+pub fn process_start_ms() -> Option<f64> {
+    // Field 22 is `starttime`, in clock ticks since boot.
+    let ticks = crate::procfs::stat_field("self", 22)?;
+    // SAFETY: sysconf has no preconditions.
+    let hz = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    (hz > 0).then(|| ticks as f64 * 1e3 / hz as f64)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn process_start_ms() -> Option<f64> {
+    None
+}
@@ -0,0 +1,112 @@
+// Per-phase deadlines for benchmark runs.
+//
+// Rather than leave a hang to the harness's kill timer, a benchmark run holds
+// itself to a deadline for each phase:
+//
+//   window_creation  from setup() until the main window is built
+//                    BENCHMARK_DEADLINE_WINDOW_MS (default 10000)
+//   page_load        from then until its page has loaded
+//                    BENCHMARK_DEADLINE_PAGE_LOAD_MS (default 10000)
+//   scenario         from then until the run finishes and exit is requested
+//                    BENCHMARK_DEADLINE_SCENARIO_MS (default 600000)
+//
+// 0 turns a deadline off. The defaults suit running the app by hand; the
+// harness sets all three so they expire before its own kill timer
+// (`tauriDeadlines` in benchmarks/utils.ts). When one expires, the watchdog thread writes the
+// "stalled" error record to stderr (exit code 9, see error.rs), prints the
+// report as far as it got to stdout with a "watchdog" section naming the
+// phase, and exits the process itself: whatever stalled may be holding the
+// main thread, so it doesn't go through the event loop.
+//
+//   "watchdog": { "phase": "page_load", "deadline_ms": 10000,
+//                 "elapsed_ms": 10000.4 }
+//
+// The interactive app has no deadlines, and BENCHMARK=control none after its
+// page has loaded (its requests have their own timeouts). The thread only
+// wakes when a deadline is due, so it doesn't disturb BENCHMARK=idle.
+
+use std::io;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::error::Error;
+use crate::mode::{BenchConfig, Mode};
+use crate::report::{Phases, Report};
+use crate::timing;
+
+/// How often to look for progress while a phase has no deadline.
+const POLL: Duration = Duration::from_millis(100);
+
+/// The phase a run is in: its name, when it began and its deadline in ms.
+/// `None` once nothing is left to watch.
+pub fn current(
+    phases: &Phases,
+    mode: Mode,
+    config: &BenchConfig,
+) -> Option<(&'static str, f64, u64)> {
+    if phases.exit_requested.is_some() {
+        return None;
+    }
+    match (phases.window_created, phases.page_loaded) {
+        (None, _) => Some(("window_creation", phases.setup?, config.deadline_window_ms)),
+        (Some(created), None) => Some(("page_load", created, config.deadline_page_load_ms)),
+        (Some(_), Some(loaded)) if mode != Mode::Control => {
+            Some(("scenario", loaded, config.deadline_scenario_ms))
+        }
+        _ => None,
+    }
+}
+
+/// Start watching the run's phases. Call from setup(), once it is marked.
+pub fn start<R: Runtime>(app: &AppHandle<R>, mode: Mode, config: &BenchConfig) {
+    if mode == Mode::Interactive {
+        return;
+    }
+    let app = app.clone();
+    let config = config.clone();
+    std::thread::spawn(move || loop {
+        let phases = app.state::<Report>().phases();
+        let Some((phase, since, deadline_ms)) = current(&phases, mode, &config) else {
+            return;
+        };
+        if deadline_ms == 0 {
+            if phase == "scenario" {
+                return;
+            }
+            std::thread::sleep(POLL);
+            continue;
+        }
+        let now = timing::now_ms();
+        let due = since + deadline_ms as f64;
+        if now < due {
+            std::thread::sleep(Duration::from_secs_f64((due - now) / 1e3));
+            continue;
+        }
+        expire(&app, phase, deadline_ms, now - since);
+    });
+}
+
+fn expire<R: Runtime>(app: &AppHandle<R>, phase: &str, deadline_ms: u64, elapsed_ms: f64) -> ! {
+    let report = app.state::<Report>();
+    let error = Error::Stalled(format!("{phase} did not finish within {deadline_ms} ms"));
+    let code = error.exit_code();
+    report.failed(error, &mut io::stderr());
+    println!(
+        "{}",
+        partial_report(&report, phase, deadline_ms, elapsed_ms)
+    );
+    std::process::exit(code)
+}
+
+/// The report as far as the run got, with the phase that overran.
+pub fn partial_report(report: &Report, phase: &str, deadline_ms: u64, elapsed_ms: f64) -> Value {
+    let mut document = report.to_json();
+    document["watchdog"] = json!({
+        "phase": phase,
+        "deadline_ms": deadline_ms,
+        "elapsed_ms": elapsed_ms,
+    });
+    document
+}
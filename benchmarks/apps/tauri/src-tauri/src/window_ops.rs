@@ -0,0 +1,211 @@
+// Window-operation latency (BENCHMARK=window-ops).
+//
+// The same operations Craft's bridge exposes as window.craft.window.* —
+// show, hide, toggle, minimize, close — plus set_title, set_size and
+// set_position, as commands acting on a separate "target" window (so hiding
+// or closing it doesn't take the driver page with it).
+//
+// Each command performs its operation and then polls the window until the
+// change is visible through its getters (or, for close, until the window is
+// gone from the manager), returning the milliseconds that took. The driver,
+// ../src/bench/window-ops.js, times the whole invoke around it; both figures
+// come back through `window_ops_report`. An operation that has no visible
+// effect within EFFECT_TIMEOUT (minimize without a window manager, say) is
+// an error and counted as a failure.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::{
+    AppHandle, LogicalPosition, LogicalSize, Manager, Runtime, State, WebviewUrl, WebviewWindow,
+    WebviewWindowBuilder,
+};
+
+use crate::mode::BenchConfig;
+use crate::report::{self, Report};
+use crate::stats::Summary;
+
+const TARGET: &str = "target";
+const EFFECT_TIMEOUT: Duration = Duration::from_secs(2);
+const POLL: Duration = Duration::from_micros(250);
+
+fn target<R: Runtime>(app: &AppHandle<R>) -> Result<WebviewWindow<R>, String> {
+    app.get_webview_window(TARGET)
+        .ok_or_else(|| "target window is not open".to_owned())
+}
+
+/// Run `op`, then wait until `done` holds; returns the elapsed milliseconds.
+fn timed(op: impl FnOnce() -> tauri::Result<()>, done: impl Fn() -> bool) -> Result<f64, String> {
+    let start = Instant::now();
+    op().map_err(|e| e.to_string())?;
+    while !done() {
+        if start.elapsed() > EFFECT_TIMEOUT {
+            return Err(format!("no effect after {EFFECT_TIMEOUT:?}"));
+        }
+        std::thread::sleep(POLL);
+    }
+    Ok(start.elapsed().as_secs_f64() * 1e3)
+}
+
+fn visible<R: Runtime>(window: &WebviewWindow<R>, expected: bool) -> bool {
+    window.is_visible().is_ok_and(|v| v == expected)
+}
+
+// The commands below block while they poll, so they run on the async
+// runtime's thread pool rather than the main thread that has to apply them.
+
+/// Open the target window if it isn't already.
+#[tauri::command(async)]
+pub fn window_open<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    if app.get_webview_window(TARGET).is_some() {
+        return Ok(());
+    }
+    WebviewWindowBuilder::new(&app, TARGET, WebviewUrl::App("index.html".into()))
+        .title("Target")
+        .inner_size(400.0, 300.0)
+        .build()
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command(async)]
+pub fn window_show<R: Runtime>(app: AppHandle<R>) -> Result<f64, String> {
+    let window = target(&app)?;
+    timed(|| window.show(), || visible(&window, true))
+}
+
+#[tauri::command(async)]
+pub fn window_hide<R: Runtime>(app: AppHandle<R>) -> Result<f64, String> {
+    let window = target(&app)?;
+    timed(|| window.hide(), || visible(&window, false))
+}
+
+#[tauri::command(async)]
+pub fn window_toggle<R: Runtime>(app: AppHandle<R>) -> Result<f64, String> {
+    let window = target(&app)?;
+    let shown = window.is_visible().map_err(|e| e.to_string())?;
+    timed(
+        || if shown { window.hide() } else { window.show() },
+        || visible(&window, !shown),
+    )
+}
+
+#[tauri::command(async)]
+pub fn window_minimize<R: Runtime>(app: AppHandle<R>) -> Result<f64, String> {
+    let window = target(&app)?;
+    timed(
+        || window.minimize(),
+        || window.is_minimized().is_ok_and(|m| m),
+    )
+}
+
+/// Not one of the measured operations: restores the window between
+/// `window_minimize` runs.
+#[tauri::command(async)]
+pub fn window_unminimize<R: Runtime>(app: AppHandle<R>) -> Result<f64, String> {
+    let window = target(&app)?;
+    timed(
+        || window.unminimize(),
+        || window.is_minimized().is_ok_and(|m| !m),
+    )
+}
+
+#[tauri::command(async)]
+pub fn window_close<R: Runtime>(app: AppHandle<R>) -> Result<f64, String> {
+    let window = target(&app)?;
+    timed(
+        || window.close(),
+        || app.get_webview_window(TARGET).is_none(),
+    )
+}
+
+#[tauri::command(async)]
+pub fn window_set_title<R: Runtime>(app: AppHandle<R>, title: String) -> Result<f64, String> {
+    let window = target(&app)?;
+    timed(
+        || window.set_title(&title),
+        || window.title().is_ok_and(|t| t == title),
+    )
+}
+
+#[tauri::command(async)]
+pub fn window_set_size<R: Runtime>(
+    app: AppHandle<R>,
+    width: f64,
+    height: f64,
+) -> Result<f64, String> {
+    let window = target(&app)?;
+    timed(
+        || window.set_size(LogicalSize::new(width, height)),
+        || {
+            let (Ok(size), Ok(scale)) = (window.inner_size(), window.scale_factor()) else {
+                return false;
+            };
+            let size = size.to_logical::<f64>(scale);
+            (size.width - width).abs() < 1.0 && (size.height - height).abs() < 1.0
+        },
+    )
+}
+
+#[tauri::command(async)]
+pub fn window_set_position<R: Runtime>(app: AppHandle<R>, x: f64, y: f64) -> Result<f64, String> {
+    let window = target(&app)?;
+    timed(
+        || window.set_position(LogicalPosition::new(x, y)),
+        || {
+            let (Ok(position), Ok(scale)) = (window.outer_position(), window.scale_factor()) else {
+                return false;
+            };
+            let position = position.to_logical::<f64>(scale);
+            (position.x - x).abs() < 1.0 && (position.y - y).abs() < 1.0
+        },
+    )
+}
+
+/// Timings for one operation as seen by the driver.
+#[derive(Debug, Deserialize)]
+pub struct OpSamples {
+    pub op: String,
+    /// Invoke round trip, including the wait for the effect.
+    pub samples: Vec<f64>,
+    /// The command's own operation-to-effect time.
+    pub effects: Vec<f64>,
+    pub failures: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct OpResult {
+    op: String,
+    failures: u64,
+    latency_ms: Option<Summary>,
+    effect_ms: Option<Summary>,
+}
+
+/// Final call from the window-ops driver: report every operation and quit.
+#[tauri::command]
+pub fn window_ops_report<R: Runtime>(
+    app: AppHandle<R>,
+    config: State<'_, BenchConfig>,
+    report: State<'_, Report>,
+    results: Vec<OpSamples>,
+) {
+    let results: Vec<OpResult> = results
+        .into_iter()
+        .map(|r| OpResult {
+            latency_ms: Summary::from_samples(&r.samples),
+            effect_ms: Summary::from_samples(&r.effects),
+            op: r.op,
+            failures: r.failures,
+        })
+        .collect();
+    report.emit(
+        "window_ops",
+        json!({
+            "scenario": "window-ops",
+            "iterations": config.window_op_iterations,
+            "results": results,
+        }),
+    );
+    report::finish(&app);
+}
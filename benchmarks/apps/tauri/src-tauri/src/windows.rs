@@ -0,0 +1,149 @@
+// Multi-window creation stress (BENCHMARK=windows).
+//
+// Once the main window has loaded, BENCHMARK_WINDOWS more 400x300 windows are
+// opened one after another with WebviewWindowBuilder, each loading the same
+// index.html. For every window we record how long `build()` took and how long
+// it was from starting the build until its page fired
+// PageLoadEvent::Finished. After the last page has loaded (plus
+// BENCHMARK_SETTLE_MS) the process tree's memory is sampled and compared with
+// a sample taken before the first extra window, giving the cost per window.
+
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::webview::PageLoadEvent;
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+
+use crate::memory::{self, Totals};
+use crate::mode::BenchConfig;
+use crate::report::{self, Report};
+use crate::stats::Summary;
+
+/// How long to wait for the extra windows' pages before giving up on them.
+const LOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Serialize)]
+pub struct WindowTiming {
+    pub label: String,
+    pub create_ms: Option<f64>,
+    /// From the start of `build()` to PageLoadEvent::Finished; null if the
+    /// page never loaded.
+    pub page_load_ms: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MemoryCost {
+    /// Main window only.
+    pub before: Option<Totals>,
+    /// Main window plus every extra window.
+    pub after: Option<Totals>,
+    pub per_window: Option<Totals>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WindowsReport {
+    pub windows: u64,
+    pub created: usize,
+    pub loaded: usize,
+    pub create_ms: Option<Summary>,
+    pub page_load_ms: Option<Summary>,
+    pub total_ms: f64,
+    pub memory: MemoryCost,
+    pub per_window: Vec<WindowTiming>,
+}
+
+/// Open the extra windows and report. Blocks, so call it off the main thread.
+pub fn run(app: &AppHandle, config: &BenchConfig) {
+    let before = memory::sample_tree().map(|m| m.total);
+    let (loaded_tx, loaded_rx) = mpsc::channel();
+    let started = Instant::now();
+
+    let mut timings = Vec::new();
+    let mut pending = Vec::new();
+    for i in 0..config.windows {
+        let label = format!("bench-{i}");
+        let loaded_tx = loaded_tx.clone();
+        let build_start = Instant::now();
+        let built = WebviewWindowBuilder::new(app, &label, WebviewUrl::App("index.html".into()))
+            .title(format!("Hello World {i}"))
+            .inner_size(400.0, 300.0)
+            .resizable(false)
+            .on_page_load(move |window, payload| {
+                if payload.event() == PageLoadEvent::Finished {
+                    let _ = loaded_tx.send((window.label().to_owned(), Instant::now()));
+                }
+            })
+            .build();
+        let create_ms = built
+            .is_ok()
+            .then(|| build_start.elapsed().as_secs_f64() * 1e3);
+        if create_ms.is_some() {
+            pending.push((label.clone(), build_start));
+        }
+        timings.push(WindowTiming {
+            label,
+            create_ms,
+            page_load_ms: None,
+        });
+    }
+    drop(loaded_tx);
+
+    // A page can finish more than once (e.g. a reload); only the first counts.
+    while !pending.is_empty() {
+        let Ok((label, at)) = loaded_rx.recv_timeout(LOAD_TIMEOUT) else {
+            break;
+        };
+        let Some(index) = pending.iter().position(|(l, _)| *l == label) else {
+            continue;
+        };
+        let (_, build_start) = pending.swap_remove(index);
+        if let Some(timing) = timings.iter_mut().find(|t| t.label == label) {
+            timing.page_load_ms = Some(at.duration_since(build_start).as_secs_f64() * 1e3);
+        }
+    }
+    let total_ms = started.elapsed().as_secs_f64() * 1e3;
+
+    std::thread::sleep(Duration::from_millis(config.settle_ms));
+    let after = memory::sample_tree().map(|m| m.total);
+    // Close them again so an instance under BENCHMARK=control can repeat the
+    // run.
+    for timing in &timings {
+        if let Some(window) = app.get_webview_window(&timing.label) {
+            let _ = window.destroy();
+        }
+    }
+
+    let create: Vec<f64> = timings.iter().filter_map(|t| t.create_ms).collect();
+    let load: Vec<f64> = timings.iter().filter_map(|t| t.page_load_ms).collect();
+    let per_window = before
+        .as_ref()
+        .zip(after.as_ref())
+        .filter(|_| !create.is_empty())
+        .map(|(b, a)| {
+            let n = create.len() as u64;
+            Totals {
+                rss_kb: a.rss_kb.saturating_sub(b.rss_kb) / n,
+                pss_kb: a.pss_kb.saturating_sub(b.pss_kb) / n,
+                uss_kb: a.uss_kb.saturating_sub(b.uss_kb) / n,
+            }
+        });
+
+    let windows = WindowsReport {
+        windows: config.windows,
+        created: create.len(),
+        loaded: load.len(),
+        create_ms: Summary::from_samples(&create),
+        page_load_ms: Summary::from_samples(&load),
+        total_ms,
+        memory: MemoryCost {
+            before,
+            after,
+            per_window,
+        },
+        per_window: timings,
+    };
+    app.state::<Report>()
+        .emit("windows", serde_json::to_value(windows).unwrap_or_default());
+    report::finish(app);
+}
@@ -0,0 +1,721 @@
+// The benchmark-mode output contract the Bun harness relies on, checked on
+// Tauri's mock runtime so it runs without a display: cargo test
+//
+// - a finished run prints "ready" on its own line and the process exits 0; a
+//   failed one writes a JSON error record instead and exits with its kind's
+//   code;
+// - BENCHMARK_REPORT=json produces one document with the fields report.rs
+//   documents, under the current SCHEMA_VERSION;
+// - the `BENCHMARK` values the harness passes select the modes it expects;
+// - every command the drivers call accepts the arguments they send and
+//   answers, or reports, in the shape they read.
+//
+// A Tauri upgrade that changes any of this fails here rather than in a
+// benchmark run.
+
+use std::io;
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tauri::ipc::{CallbackFn, InvokeBody, InvokeResponseBody};
+use tauri::test::{
+    get_ipc_response, mock_builder, mock_context, noop_assets, MockRuntime, INVOKE_KEY,
+};
+use tauri::webview::InvokeRequest;
+use tauri::{App, Listener, Manager, WebviewWindow, WebviewWindowBuilder};
+
+use tauri_hello_world_lib::control;
+use tauri_hello_world_lib::error::Error;
+use tauri_hello_world_lib::mode::{BenchConfig, Mode, Ready};
+use tauri_hello_world_lib::report::{Outcome, Phase, Report, READY_LINE, SCHEMA_VERSION};
+use tauri_hello_world_lib::{register, run_until_exit};
+
+fn mock_app() -> (App<MockRuntime>, WebviewWindow<MockRuntime>) {
+    let app = register(mock_builder(), Report::with_json(Mode::Ipc, false))
+        .build(mock_context(noop_assets()))
+        .expect("failed to build the mock app");
+    let window = WebviewWindowBuilder::new(&app, "main", Default::default())
+        .build()
+        .expect("failed to create the mock window");
+    (app, window)
+}
+
+fn call(
+    window: &WebviewWindow<MockRuntime>,
+    cmd: &str,
+    body: InvokeBody,
+) -> Result<InvokeResponseBody, Value> {
+    get_ipc_response(
+        window,
+        InvokeRequest {
+            cmd: cmd.into(),
+            callback: CallbackFn(0),
+            error: CallbackFn(1),
+            url: "http://tauri.localhost".parse().unwrap(),
+            body,
+            headers: Default::default(),
+            invoke_key: INVOKE_KEY.to_string(),
+        },
+    )
+}
+
+/// Invoke `cmd` with JSON `args` and decode its JSON answer.
+fn invoke(window: &WebviewWindow<MockRuntime>, cmd: &str, args: Value) -> Result<Value, Value> {
+    call(window, cmd, InvokeBody::Json(args)).map(|body| body.deserialize().unwrap())
+}
+
+/// Redirect the app's report so report commands can be called without the
+/// app exiting.
+fn capture(app: &App<MockRuntime>) -> Receiver<Outcome> {
+    let (tx, rx) = mpsc::channel();
+    app.state::<Report>().redirect(Some(tx));
+    rx
+}
+
+/// The section a report command emitted, checking it then finished the run.
+fn reported(rx: &Receiver<Outcome>, name: &str) -> Value {
+    let timeout = Duration::from_secs(5);
+    let value = loop {
+        match rx.recv_timeout(timeout).expect("no section reported") {
+            Outcome::Section(section, value) if section == name => break value,
+            Outcome::Section(..) => continue,
+            Outcome::Finished => panic!("finished without a {name:?} section"),
+            Outcome::Failed(error) => panic!("{name} failed: {error}"),
+        }
+    };
+    assert!(
+        matches!(rx.recv_timeout(timeout), Ok(Outcome::Finished)),
+        "{name} did not finish the run"
+    );
+    value
+}
+
+fn keys(value: &Value) -> Vec<&str> {
+    let mut keys: Vec<&str> = value
+        .as_object()
+        .unwrap_or_else(|| panic!("not an object: {value}"))
+        .keys()
+        .map(String::as_str)
+        .collect();
+    keys.sort_unstable();
+    keys
+}
+
+const SUMMARY_KEYS: &[&str] = &["count", "max", "mean", "min", "p50", "p90", "p99"];
+
+#[test]
+fn finished_run_prints_ready_and_exits_0() {
+    let report = Report::with_json(Mode::Startup(Ready::Load), false);
+    let mut out = Vec::new();
+    assert_eq!(report.finished(&mut out), Some(0));
+    assert_eq!(READY_LINE, "ready");
+    assert_eq!(out, b"ready\n");
+}
+
+#[test]
+fn redirected_run_neither_prints_nor_exits() {
+    let report = Report::with_json(Mode::Ipc, false);
+    let (tx, rx) = mpsc::channel();
+    report.redirect(Some(tx));
+    let mut out = Vec::new();
+    assert_eq!(report.finished(&mut out), None);
+    assert!(out.is_empty());
+    assert!(matches!(rx.try_recv(), Ok(Outcome::Finished)));
+}
+
+#[test]
+fn failed_run_writes_an_error_record_and_exits_with_its_code() {
+    let report = Report::with_json(Mode::Scenario, true);
+    let mut out = Vec::new();
+    let error = Error::Timeout("page did not answer invoke".into());
+    assert_eq!(report.failed(error, &mut out), Some(8));
+
+    let record = json!({
+        "event": "error",
+        "kind": "timeout",
+        "exit_code": 8,
+        "message": "page did not answer invoke",
+    });
+    let out = String::from_utf8(out).unwrap();
+    assert_eq!(out.lines().count(), 1);
+    assert_eq!(serde_json::from_str::<Value>(&out).unwrap(), record);
+    assert_eq!(report.exited().unwrap()["error"], record);
+}
+
+#[test]
+fn redirected_failure_goes_to_the_sink() {
+    let report = Report::with_json(Mode::Ipc, false);
+    let (tx, rx) = mpsc::channel();
+    report.redirect(Some(tx));
+    let mut out = Vec::new();
+    assert_eq!(report.failed(Error::Scenario("x".into()), &mut out), None);
+    assert!(out.is_empty());
+    assert!(matches!(
+        rx.try_recv(),
+        Ok(Outcome::Failed(Error::Scenario(_)))
+    ));
+}
+
+/// The code the process exits with after `end` is reported and the event
+/// loop winds down. (`report::finish` and `report::fail` can't be called
+/// here: the mock runtime doesn't implement exit requests, so the last window
+/// is closed instead.)
+fn exit_code_after(end: fn(&Report)) -> i32 {
+    let app = register(mock_builder(), Report::with_json(Mode::Ipc, false))
+        .setup(move |app| {
+            end(&app.state::<Report>());
+            app.get_webview_window("main").unwrap().destroy()?;
+            Ok(())
+        })
+        .build(mock_context(noop_assets()))
+        .expect("failed to build the mock app");
+    WebviewWindowBuilder::new(&app, "main", Default::default())
+        .build()
+        .expect("failed to create the mock window");
+    run_until_exit(app)
+}
+
+#[test]
+fn process_exits_with_the_runs_code() {
+    let finished = |report: &Report| {
+        report.finished(&mut io::sink());
+    };
+    let failed = |report: &Report| {
+        report.failed(Error::Config("no scenario".into()), &mut io::sink());
+    };
+    assert_eq!(exit_code_after(finished), 0);
+    assert_eq!(exit_code_after(failed), 2);
+}
+
+#[test]
+fn exit_codes_are_stable() {
+    let message = String::new;
+    for (error, kind, code) in [
+        (Error::Config(message()), "config", 2),
+        (Error::Setup(message()), "setup", 3),
+        (Error::Window(message()), "window", 4),
+        (Error::PageLoad(message()), "page_load", 5),
+        (Error::Webview(message()), "webview", 6),
+        (Error::Scenario(message()), "scenario", 7),
+        (Error::Timeout(message()), "timeout", 8),
+        (Error::Stalled(message()), "stalled", 9),
+    ] {
+        assert_eq!((error.kind(), error.exit_code()), (kind, code));
+    }
+}
+
+#[test]
+fn json_report_schema() {
+    let report = Report::with_json(Mode::Startup(Ready::Paint), true);
+    for phase in [
+        Phase::Main,
+        Phase::Builder,
+        Phase::Setup,
+        Phase::WindowCreated,
+        Phase::PageLoaded,
+        Phase::ExitRequested,
+    ] {
+        assert!(report.mark(phase));
+        assert!(!report.mark(phase), "{phase:?} counted twice");
+    }
+    report.emit("paint", json!({ "paint_ms": 12.5 }));
+    report.annotate("rendering", json!({ "platform": "test" }));
+
+    let document = report.exited().expect("no document in JSON-report mode");
+    assert_eq!(
+        keys(&document),
+        [
+            "clock",
+            "mode",
+            "paint",
+            "phases",
+            "ready",
+            "rendering",
+            "schema_version",
+            "shutdown"
+        ]
+    );
+    assert_eq!(document["schema_version"], SCHEMA_VERSION);
+    assert_eq!(document["mode"], "startup");
+    assert_eq!(document["ready"], "paint");
+    assert!(document["clock"].is_string());
+    assert_eq!(document["paint"], json!({ "paint_ms": 12.5 }));
+
+    let phases = &document["phases"];
+    assert_eq!(
+        keys(phases),
+        [
+            "builder",
+            "exit",
+            "exit_requested",
+            "main",
+            "page_loaded",
+            "process_start",
+            "setup",
+            "window_created"
+        ]
+    );
+    let reached: Vec<f64> = [
+        "main",
+        "builder",
+        "setup",
+        "window_created",
+        "page_loaded",
+        "exit_requested",
+        "exit",
+    ]
+    .iter()
+    .map(|phase| phases[phase].as_f64().expect("phase not recorded"))
+    .collect();
+    assert!(reached.windows(2).all(|w| w[0] <= w[1]), "{phases}");
+
+    assert_eq!(keys(&document["shutdown"]), ["shutdown_ms", "startup_ms"]);
+    assert!(document["shutdown"]["startup_ms"].as_f64().unwrap() >= 0.0);
+    assert!(document["shutdown"]["shutdown_ms"].as_f64().unwrap() >= 0.0);
+}
+
+#[test]
+fn legacy_report_prints_no_document() {
+    let report = Report::with_json(Mode::Startup(Ready::Load), false);
+    report.mark(Phase::Main);
+    assert_eq!(report.exited(), None);
+
+    let report = Report::with_json(Mode::Ipc, true);
+    assert_eq!(report.to_json()["ready"], Value::Null);
+}
+
+#[test]
+fn benchmark_values_select_their_modes() {
+    assert_eq!(Mode::from_name("1").name(), "startup");
+    assert_eq!(Mode::from_name(""), Mode::Interactive);
+    for name in [
+        "ipc",
+        "memory",
+        "memory-series",
+        "idle",
+        "payload",
+        "events",
+        "stream",
+        "assets",
+        "synthetic",
+        "windows",
+        "window-ops",
+        "eval",
+        "scenario",
+        "rendering",
+        "control",
+    ] {
+        assert_eq!(Mode::from_name(name).name(), name);
+    }
+}
+
+#[test]
+fn update_title_acks_the_shared_payload() {
+    let (_app, window) = mock_app();
+    let data = json!({
+        "title": "Hello World",
+        "timestamp": 1708200000000u64,
+        "metadata": { "source": "renderer", "priority": 1 },
+    });
+    assert_eq!(
+        invoke(&window, "update_title", json!({ "data": data })),
+        Ok(json!({ "ok": true }))
+    );
+    assert!(invoke(&window, "update_title", json!({ "data": { "title": 1 } })).is_err());
+}
+
+#[test]
+fn echo_returns_its_value() {
+    let (_app, window) = mock_app();
+    let value = json!({ "nested": [1, "two", null] });
+    assert_eq!(
+        invoke(&window, "echo", json!({ "value": value })),
+        Ok(value)
+    );
+}
+
+#[test]
+fn bench_config_is_camel_case() {
+    let (_app, window) = mock_app();
+    let config = invoke(&window, "bench_config", json!({})).unwrap();
+    for key in [
+        "iterations",
+        "warmup",
+        "settleMs",
+        "durationMs",
+        "intervalMs",
+        "payloadSizes",
+        "payloadIterations",
+        "eventRate",
+        "streamChunks",
+        "streamChunkBytes",
+        "assetCount",
+        "assetSizes",
+        "embeddedAssetSizes",
+        "windows",
+        "windowOpIterations",
+        "evalSizes",
+        "evalIterations",
+        "probeIntervalMs",
+        "scenarioFile",
+        "deadlineWindowMs",
+        "deadlinePageLoadMs",
+        "deadlineScenarioMs",
+    ] {
+        assert!(
+            config.get(key).is_some(),
+            "bench_config lacks {key}: {config}"
+        );
+    }
+    assert!(config["payloadSizes"].is_array());
+}
+
+#[test]
+fn payload_commands_round_trip() {
+    let (_app, window) = mock_app();
+    let text = "x".repeat(1024);
+    assert_eq!(
+        invoke(&window, "payload_string", json!({ "data": text })),
+        Ok(json!(text))
+    );
+    let bytes = vec![7u8; 1024];
+    assert_eq!(
+        invoke(&window, "payload_bytes", json!({ "data": bytes })),
+        Ok(json!(bytes))
+    );
+
+    match call(&window, "payload_raw", InvokeBody::Raw(bytes.clone())) {
+        Ok(InvokeResponseBody::Raw(echoed)) => assert_eq!(echoed, bytes),
+        other => panic!("payload_raw answered {other:?}"),
+    }
+    assert!(invoke(&window, "payload_raw", json!({ "data": [1, 2, 3] })).is_err());
+}
+
+#[test]
+fn callback_commands_answer_null() {
+    let (_app, window) = mock_app();
+    assert_eq!(
+        invoke(&window, "eval_ack", json!({ "id": 1, "len": 10 })),
+        Ok(Value::Null)
+    );
+    assert_eq!(
+        invoke(&window, "scenario_reply", json!({ "id": 1, "result": {} })),
+        Ok(Value::Null)
+    );
+}
+
+#[test]
+fn bench_fail_fails_the_run() {
+    let (app, window) = mock_app();
+    let rx = capture(&app);
+    assert_eq!(
+        invoke(
+            &window,
+            "bench_fail",
+            json!({ "message": "ipc benchmark failed" })
+        ),
+        Ok(Value::Null)
+    );
+    match rx.recv_timeout(Duration::from_secs(5)) {
+        Ok(Outcome::Failed(Error::Scenario(message))) => {
+            assert_eq!(message, "ipc benchmark failed")
+        }
+        _ => panic!("bench_fail did not fail the run"),
+    }
+}
+
+#[test]
+fn events_start_emits_ticks() {
+    let (app, window) = mock_app();
+    let (tx, rx) = mpsc::channel();
+    app.listen_any("bench:tick", move |event| {
+        let _ = tx.send(serde_json::from_str::<Value>(event.payload()).unwrap());
+    });
+    assert_eq!(invoke(&window, "events_start", json!({})), Ok(Value::Null));
+
+    let tick = rx.recv_timeout(Duration::from_secs(5)).expect("no tick");
+    assert_eq!(keys(&tick), ["sentAt", "seq"]);
+    assert_eq!(tick["seq"], 0);
+}
+
+#[test]
+fn stream_emit_ends_with_its_count() {
+    let (app, window) = mock_app();
+    let (tx, rx) = mpsc::channel();
+    app.listen_any("bench:chunk-end", move |event| {
+        let _ = tx.send(serde_json::from_str::<Value>(event.payload()).unwrap());
+    });
+    assert_eq!(invoke(&window, "stream_emit", json!({})), Ok(Value::Null));
+
+    let end = rx
+        .recv_timeout(Duration::from_secs(30))
+        .expect("no chunk-end");
+    assert_eq!(keys(&end), ["elapsedMs", "sent"]);
+    assert_eq!(end["sent"], app.state::<BenchConfig>().stream_chunks);
+}
+
+#[test]
+fn stream_channel_takes_the_pages_channel() {
+    let (_app, window) = mock_app();
+    // A `Channel` argument arrives as the id the JS side serializes it to.
+    assert_eq!(
+        invoke(
+            &window,
+            "stream_channel",
+            json!({ "channel": "__CHANNEL__:7" })
+        ),
+        Ok(Value::Null)
+    );
+    assert!(invoke(&window, "stream_channel", json!({ "channel": 7 })).is_err());
+}
+
+#[test]
+fn window_open_creates_the_target_once() {
+    let (app, window) = mock_app();
+    assert_eq!(invoke(&window, "window_open", json!({})), Ok(Value::Null));
+    assert!(app.get_webview_window("target").is_some());
+    assert_eq!(invoke(&window, "window_open", json!({})), Ok(Value::Null));
+    assert!(invoke(&window, "window_show", json!({}))
+        .unwrap()
+        .is_number());
+}
+
+#[test]
+fn window_ops_fail_without_their_target() {
+    let (_app, window) = mock_app();
+    for (cmd, args) in [
+        ("window_show", json!({})),
+        ("window_hide", json!({})),
+        ("window_toggle", json!({})),
+        ("window_minimize", json!({})),
+        ("window_unminimize", json!({})),
+        ("window_close", json!({})),
+        ("window_set_title", json!({ "title": "t" })),
+        (
+            "window_set_size",
+            json!({ "width": 400.0, "height": 300.0 }),
+        ),
+        ("window_set_position", json!({ "x": 10.0, "y": 10.0 })),
+    ] {
+        assert_eq!(
+            invoke(&window, cmd, args),
+            Err(json!("target window is not open")),
+            "{cmd}"
+        );
+    }
+}
+
+#[test]
+fn ipc_report_shape() {
+    let (app, window) = mock_app();
+    let rx = capture(&app);
+    let results = json!([{ "command": "echo", "samples": [0.5, 1.0, 1.5] }]);
+    assert_eq!(
+        invoke(
+            &window,
+            "ipc_report",
+            json!({ "results": results, "timerResolutionMs": 0.1 })
+        ),
+        Ok(Value::Null)
+    );
+
+    let ipc = reported(&rx, "ipc");
+    assert_eq!(keys(&ipc), ["commands", "iterations", "scenario", "warmup"]);
+    assert_eq!(ipc["scenario"], "ipc");
+    let command = &ipc["commands"][0];
+    assert_eq!(
+        keys(command),
+        ["command", "latency_histogram", "latency_ms"]
+    );
+    assert_eq!(command["command"], "echo");
+    assert_eq!(keys(&command["latency_ms"]), SUMMARY_KEYS);
+    assert_eq!(command["latency_ms"]["count"], 3);
+
+    // 100 µs timer steps: buckets are 128 µs wide where the HDR ones would
+    // be finer.
+    let histogram = &command["latency_histogram"];
+    assert_eq!(histogram["resolution_ms"], 0.1);
+    assert_eq!(
+        histogram["buckets"],
+        json!([[0.511, 1], [1.023, 1], [1.535, 1]])
+    );
+}
+
+#[test]
+fn paint_report_shape() {
+    let (app, window) = mock_app();
+    let rx = capture(&app);
+    let timings = json!({
+        "timeOrigin": 1708200000000.0,
+        "paint": 40.0,
+        "domContentLoaded": 30.0,
+        "load": 35.0,
+    });
+    assert_eq!(
+        invoke(&window, "paint_report", json!({ "timings": timings })),
+        Ok(Value::Null)
+    );
+    assert_eq!(
+        keys(&reported(&rx, "paint")),
+        [
+            "dom_content_loaded_ms",
+            "load_ms",
+            "paint_ms",
+            "time_origin_ms"
+        ]
+    );
+}
+
+#[test]
+fn payload_report_shape() {
+    let (app, window) = mock_app();
+    let rx = capture(&app);
+    let results = json!([{ "form": "string", "bytes": 1024, "samples": [1.0, 2.0] }]);
+    assert_eq!(
+        invoke(&window, "payload_report", json!({ "results": results })),
+        Ok(Value::Null)
+    );
+
+    let payload = reported(&rx, "payload");
+    assert_eq!(keys(&payload), ["iterations", "results", "scenario"]);
+    assert_eq!(payload["scenario"], "payload");
+    assert_eq!(
+        keys(&payload["results"][0]),
+        ["bytes", "form", "latency_ms", "throughput_mb_s"]
+    );
+}
+
+#[test]
+fn events_report_shape() {
+    let (app, window) = mock_app();
+    let rx = capture(&app);
+    let samples = json!({
+        "sent": 3,
+        "seqs": [0, 1, 2],
+        "arrivals": [0.0, 1.0, 2.0],
+        "latencies": [0.2, 0.3, 0.4],
+    });
+    assert_eq!(
+        invoke(&window, "events_report", json!({ "samples": samples })),
+        Ok(Value::Null)
+    );
+
+    let events = reported(&rx, "events");
+    for key in [
+        "rate_hz",
+        "sent",
+        "inter_arrival_ms",
+        "jitter_ms",
+        "latency_ms",
+        "inter_arrival_histogram",
+        "latency_histogram",
+    ] {
+        assert!(events.get(key).is_some(), "events lacks {key}: {events}");
+    }
+    assert_eq!(events["sent"], 3);
+}
+
+#[test]
+fn stream_report_shape() {
+    let (app, window) = mock_app();
+    let rx = capture(&app);
+    let results = json!([{
+        "transport": "channel",
+        "sent": 2,
+        "startedAt": 0.0,
+        "seqs": [0, 1],
+        "arrivals": [1.0, 2.0],
+        "heldBack": 1,
+    }]);
+    assert_eq!(
+        invoke(&window, "stream_report", json!({ "results": results })),
+        Ok(Value::Null)
+    );
+
+    let stream = reported(&rx, "stream");
+    assert_eq!(
+        keys(&stream),
+        ["chunk_bytes", "chunks", "results", "scenario"]
+    );
+    assert_eq!(stream["results"][0]["transport"], "channel");
+    assert_eq!(stream["results"][0]["received"], 2);
+    assert_eq!(stream["results"][0]["held_back"], 1);
+}
+
+#[test]
+fn assets_report_shape() {
+    let (app, window) = mock_app();
+    let rx = capture(&app);
+    let results = json!([{
+        "source": "bench",
+        "bytes": 1024,
+        "sequential": [0.5, 0.6],
+        "parallelMs": 1.0,
+    }]);
+    assert_eq!(
+        invoke(&window, "assets_report", json!({ "results": results })),
+        Ok(Value::Null)
+    );
+
+    let assets = reported(&rx, "assets");
+    assert_eq!(keys(&assets), ["count", "results", "scenario"]);
+    assert_eq!(
+        keys(&assets["results"][0]),
+        [
+            "bytes",
+            "count",
+            "latency_ms",
+            "parallel_ms",
+            "source",
+            "throughput_mb_s"
+        ]
+    );
+}
+
+#[test]
+fn assets_run_over_the_control_channel() {
+    let (app, window) = mock_app();
+    let handle = app.handle().clone();
+    let run = std::thread::spawn(move || control::run(&handle, "assets", |_, _| {}));
+
+    // Play the page: once navigated it loads, and its driver reports.
+    let deadline = std::time::Instant::now() + Duration::from_secs(5);
+    while !window.url().unwrap().path().ends_with("assets.html") {
+        assert!(std::time::Instant::now() < deadline, "never navigated");
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    control::page_loaded(app.handle());
+    let results = json!([{
+        "source": "bench",
+        "bytes": 1024,
+        "sequential": [0.5],
+        "parallelMs": 1.0,
+    }]);
+    assert_eq!(
+        invoke(&window, "assets_report", json!({ "results": results })),
+        Ok(Value::Null)
+    );
+
+    let result = run.join().unwrap().expect("assets failed over control");
+    assert_eq!(result["assets"]["scenario"], "assets");
+}
+
+#[test]
+fn window_ops_report_shape() {
+    let (app, window) = mock_app();
+    let rx = capture(&app);
+    let results = json!([{ "op": "show", "samples": [1.0], "effects": [0.5], "failures": 0 }]);
+    assert_eq!(
+        invoke(&window, "window_ops_report", json!({ "results": results })),
+        Ok(Value::Null)
+    );
+
+    let ops = reported(&rx, "window_ops");
+    assert_eq!(keys(&ops), ["iterations", "results", "scenario"]);
+    assert_eq!(ops["scenario"], "window-ops");
+    assert_eq!(
+        keys(&ops["results"][0]),
+        ["effect_ms", "failures", "latency_ms", "op"]
+    );
+}
@@ -0,0 +1,92 @@
+// The watchdog's phase selection and the partial report it prints when a
+// deadline expires (see src/watchdog.rs): cargo test
+
+use std::io;
+
+use serde_json::json;
+
+use tauri_hello_world_lib::error::Error;
+use tauri_hello_world_lib::mode::{BenchConfig, Mode, Ready};
+use tauri_hello_world_lib::report::{Phase, Phases, Report, SCHEMA_VERSION};
+use tauri_hello_world_lib::watchdog::{current, partial_report};
+
+fn config() -> BenchConfig {
+    BenchConfig {
+        deadline_window_ms: 1,
+        deadline_page_load_ms: 2,
+        deadline_scenario_ms: 3,
+        ..BenchConfig::from_env()
+    }
+}
+
+#[test]
+fn nothing_to_watch_before_setup() {
+    assert_eq!(current(&Phases::default(), Mode::Ipc, &config()), None);
+}
+
+#[test]
+fn each_phase_runs_from_the_end_of_the_last() {
+    let config = config();
+    let mut phases = Phases {
+        setup: Some(10.0),
+        ..Phases::default()
+    };
+    assert_eq!(
+        current(&phases, Mode::Ipc, &config),
+        Some(("window_creation", 10.0, 1))
+    );
+
+    phases.window_created = Some(20.0);
+    assert_eq!(
+        current(&phases, Mode::Ipc, &config),
+        Some(("page_load", 20.0, 2))
+    );
+
+    phases.page_loaded = Some(30.0);
+    assert_eq!(
+        current(&phases, Mode::Ipc, &config),
+        Some(("scenario", 30.0, 3))
+    );
+
+    phases.exit_requested = Some(40.0);
+    assert_eq!(current(&phases, Mode::Ipc, &config), None);
+}
+
+#[test]
+fn control_has_no_scenario_deadline() {
+    let config = config();
+    let mut phases = Phases {
+        setup: Some(10.0),
+        window_created: Some(20.0),
+        ..Phases::default()
+    };
+    assert_eq!(
+        current(&phases, Mode::Control, &config),
+        Some(("page_load", 20.0, 2))
+    );
+    phases.page_loaded = Some(30.0);
+    assert_eq!(current(&phases, Mode::Control, &config), None);
+}
+
+#[test]
+fn partial_report_names_the_phase() {
+    for json in [true, false] {
+        let report = Report::with_json(Mode::Startup(Ready::Load), json);
+        report.mark(Phase::Setup);
+        report.mark(Phase::WindowCreated);
+        let error = Error::Stalled("page_load did not finish within 10000 ms".into());
+        assert_eq!(report.failed(error, &mut io::sink()), Some(9));
+
+        let document = partial_report(&report, "page_load", 10_000, 10_000.5);
+        assert_eq!(
+            document["watchdog"],
+            json!({ "phase": "page_load", "deadline_ms": 10_000, "elapsed_ms": 10_000.5 })
+        );
+        assert_eq!(document["schema_version"], SCHEMA_VERSION);
+        assert!(document["phases"]["window_created"].is_number());
+        assert!(document["phases"]["page_loaded"].is_null());
+        if json {
+            assert_eq!(document["error"]["kind"], "stalled");
+        }
+    }
+}
@@ -0,0 +1,70 @@
+/**
+ * Asset delivery driver (BENCHMARK=assets)
+ *
+ * For every configured size, fetches `assetCount` generated assets from each
+ * custom scheme, first one after another (per-request latency) and then all
+ * at once (batch time), checking every body arrives at the requested size:
+ *
+ *   bench        register_uri_scheme_protocol
+ *   bench-async  register_asynchronous_uri_scheme_protocol
+ *
+ * The `embedded` rows fetch the fixture of the same size that build.rs
+ * embedded from ../src/fixtures through the built-in protocol, as the
+ * baseline for assets bundled with the app; sizes without a fixture have no
+ * baseline.
+ * Scheme URLs come from convertFileSrc so the Windows/Android
+ * `http://<scheme>.localhost` form is handled. Samples go back to Rust via
+ * `assets_report`.
+ */
+import { internals, invoke } from './util.js'
+
+const SCHEMES = ['bench', 'bench-async']
+
+async function load(url, size) {
+  const res = await fetch(url, { cache: 'no-store' })
+  const body = await res.arrayBuffer()
+  if (!res.ok || body.byteLength !== size) {
+    throw new Error(`${url}: status ${res.status}, ${body.byteLength} bytes (wanted ${size})`)
+  }
+  return body.byteLength
+}
+
+async function measure(source, bytes, count, url, size) {
+  await load(url(-1), size) // warm-up
+
+  const sequential = []
+  for (let n = 0; n < count; n++) {
+    const start = performance.now()
+    await load(url(n), size)
+    sequential.push(performance.now() - start)
+  }
+
+  const start = performance.now()
+  await Promise.all(Array.from({ length: count }, (_, n) => load(url(count + n), size)))
+  const parallelMs = performance.now() - start
+
+  return { source, bytes, sequential, parallelMs }
+}
+
+async function main() {
+  const { assetCount, assetSizes, embeddedAssetSizes } = await invoke('bench_config')
+
+  const results = []
+  for (const size of assetSizes) {
+    for (const scheme of SCHEMES) {
+      const url = n => internals.convertFileSrc(`${size}-${n}`, scheme)
+      results.push(await measure(scheme, size, assetCount, url, size))
+    }
+    if (embeddedAssetSizes.includes(size)) {
+      const url = n => `/fixtures/${size}.bin?${n}`
+      results.push(await measure('embedded', size, assetCount, url, size))
+    }
+  }
+
+  await invoke('assets_report', { results })
+}
+
+main().catch(err => {
+  console.error('assets benchmark failed:', err)
+  invoke('bench_fail', { message: `assets benchmark failed: ${err}` })
+})
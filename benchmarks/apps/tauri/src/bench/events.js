@@ -0,0 +1,45 @@
+/**
+ * Event streaming driver (BENCHMARK=events)
+ *
+ * Listens for `bench:tick` events emitted from Rust, recording each one's
+ * sequence number, arrival time and one-way latency (sentAt is Unix epoch
+ * milliseconds, compared against timeOrigin + now). When `bench:done`
+ * arrives, everything is handed to `events_report` for analysis, along with
+ * the resolution of performance.now().
+ *
+ * Listeners are registered through the core event plugin directly (see
+ * `listen` in util.js), so no JS bundle is needed.
+ */
+import { invoke, listen, timerResolution } from './util.js'
+
+async function main() {
+  const seqs = []
+  const arrivals = []
+  const latencies = []
+
+  await listen('bench:tick', ({ payload }) => {
+    const now = performance.now()
+    seqs.push(payload.seq)
+    arrivals.push(now)
+    latencies.push(performance.timeOrigin + now - payload.sentAt)
+  })
+
+  let finished
+  const done = new Promise((resolve) => {
+    finished = resolve
+  })
+  await listen('bench:done', ({ payload }) => finished(payload))
+
+  await invoke('events_start')
+  const { sent } = await done
+
+  const timerResolutionMs = timerResolution()
+  await invoke('events_report', {
+    samples: { sent, seqs, arrivals, latencies, timerResolutionMs },
+  })
+}
+
+main().catch(err => {
+  console.error('events benchmark failed:', err)
+  invoke('bench_fail', { message: `events benchmark failed: ${err}` })
+})
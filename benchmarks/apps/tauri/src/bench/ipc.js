@@ -0,0 +1,54 @@
+/**
+ * IPC round-trip driver (BENCHMARK=ipc)
+ *
+ * Invokes each registered command through Tauri's real invoke path
+ * (webview -> Rust -> webview) and times every call with performance.now().
+ * The raw samples go back to Rust via `ipc_report`, with the timer's
+ * resolution, which prints the summary and quits the app.
+ *
+ * Talks to Tauri through the helpers in util.js.
+ */
+import { invoke, timerResolution } from './util.js'
+
+// Shared test data — identical to DATA in ipc.bench.ts
+const DATA = {
+  title: 'Hello World',
+  timestamp: 1708200000000,
+  metadata: { source: 'renderer', priority: 1 },
+}
+
+const COMMANDS = [
+  { command: 'update_title', args: { data: DATA } },
+  { command: 'echo', args: { value: DATA } },
+]
+
+async function measure(command, args, warmup, iterations) {
+  for (let i = 0; i < warmup; i++) {
+    await invoke(command, args)
+  }
+
+  const samples = []
+  for (let i = 0; i < iterations; i++) {
+    const start = performance.now()
+    await invoke(command, args)
+    samples.push(performance.now() - start)
+  }
+  return samples
+}
+
+async function main() {
+  const { iterations, warmup } = await invoke('bench_config')
+
+  const results = []
+  for (const { command, args } of COMMANDS) {
+    results.push({ command, samples: await measure(command, args, warmup, iterations) })
+  }
+
+  await invoke('ipc_report', { results, timerResolutionMs: timerResolution() })
+}
+
+// Fail the run rather than leave the harness waiting for a report.
+main().catch(err => {
+  console.error('ipc benchmark failed:', err)
+  invoke('bench_fail', { message: `ipc benchmark failed: ${err}` })
+})
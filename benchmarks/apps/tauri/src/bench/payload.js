@@ -0,0 +1,75 @@
+/**
+ * Payload scaling driver (BENCHMARK=payload)
+ *
+ * Round-trips payloads of each configured size through three encodings:
+ *
+ *   string  one JSON string                 -> payload_string(String)
+ *   array   JSON array of numbers           -> payload_bytes(Vec<u8>)
+ *   raw     Uint8Array as the request body  -> payload_raw (InvokeBody::Raw),
+ *           answered with a raw ArrayBuffer (tauri::ipc::Response)
+ *
+ * Payloads are built before timing starts; each call is checked to come back
+ * at the size it was sent. Samples go back to Rust via `payload_report`.
+ */
+import { invoke } from './util.js'
+
+function bytes(size) {
+  const buf = new Uint8Array(size)
+  for (let i = 0; i < size; i++) buf[i] = i & 0xFF
+  return buf
+}
+
+const FORMS = [
+  {
+    form: 'string',
+    command: 'payload_string',
+    make: size => ({ data: 'x'.repeat(size) }),
+    length: res => res.length,
+  },
+  {
+    form: 'array',
+    command: 'payload_bytes',
+    make: size => ({ data: Array.from(bytes(size)) }),
+    length: res => res.length,
+  },
+  {
+    form: 'raw',
+    command: 'payload_raw',
+    make: size => bytes(size),
+    length: res => res.byteLength,
+  },
+]
+
+async function roundTrip(command, args, length, size) {
+  const res = await invoke(command, args)
+  if (length(res) !== size) {
+    throw new Error(`${command}: sent ${size} bytes, got ${length(res)} back`)
+  }
+}
+
+async function main() {
+  const { payloadSizes, payloadIterations } = await invoke('bench_config')
+
+  const results = []
+  for (const size of payloadSizes) {
+    for (const { form, command, make, length } of FORMS) {
+      const args = make(size)
+      await roundTrip(command, args, length, size) // warm-up
+
+      const samples = []
+      for (let i = 0; i < payloadIterations; i++) {
+        const start = performance.now()
+        await roundTrip(command, args, length, size)
+        samples.push(performance.now() - start)
+      }
+      results.push({ form, bytes: size, samples })
+    }
+  }
+
+  await invoke('payload_report', { results })
+}
+
+main().catch(err => {
+  console.error('payload benchmark failed:', err)
+  invoke('bench_fail', { message: `payload benchmark failed: ${err}` })
+})
@@ -0,0 +1,87 @@
+/**
+ * Scenario step runner (BENCHMARK=scenario)
+ *
+ * Does the in-page half of the steps in a scenario file. Rust evals
+ * `window.__scenario.run({ id, op, ... })` for each step that needs the page
+ * and waits for the answer, which comes back through `scenario_reply` with
+ * the same id:
+ *
+ *   invoke   call `command` `iterations` times with `args`; the per-call
+ *            round trips and the resolution of performance.now()
+ *   paint    wait for the next painted frame (double rAF); its time since
+ *            the page's time origin
+ *   listen   start recording `event`, answering once listening; the events
+ *            themselves are collected by a later `collect`
+ *   collect  wait for `event`'s end marker (`<event>:done`) and hand over
+ *            what arrived: count, one-way latencies (sentAt is Unix epoch
+ *            milliseconds) and the resolution of performance.now()
+ *
+ * A failing step answers `{ error }` instead.
+ */
+import { invoke, listen, timerResolution } from './util.js'
+
+const unlisten = (event, eventId) => invoke('plugin:event|unlisten', { event, eventId })
+
+const nextFrame = () => new Promise(resolve => requestAnimationFrame(resolve))
+
+/** Recordings started by `listen`, by event name. */
+const recordings = new Map()
+
+const OPS = {
+  async invoke({ command, iterations, args }) {
+    const samples = []
+    for (let i = 0; i < iterations; i++) {
+      const start = performance.now()
+      await invoke(command, args)
+      samples.push(performance.now() - start)
+    }
+    return { samples, timerResolutionMs: timerResolution() }
+  },
+
+  async paint() {
+    await nextFrame()
+    await nextFrame()
+    return { paintMs: performance.now() }
+  },
+
+  async listen({ event }) {
+    const rec = { latencies: [], ids: [] }
+    rec.done = new Promise((resolve) => {
+      rec.finish = resolve
+    })
+    rec.ids.push(await listen(event, ({ payload }) => {
+      rec.latencies.push(performance.timeOrigin + performance.now() - payload.sentAt)
+    }))
+    rec.ids.push(await listen(`${event}:done`, ({ payload }) => rec.finish(payload)))
+    recordings.set(event, rec)
+    return { listening: true }
+  },
+
+  async collect({ event }) {
+    const rec = recordings.get(event)
+    if (!rec) throw new Error(`not listening for ${event}`)
+    const { sent } = await rec.done
+    recordings.delete(event)
+    await unlisten(event, rec.ids[0])
+    await unlisten(`${event}:done`, rec.ids[1])
+    return {
+      sent,
+      received: rec.latencies.length,
+      latencies: rec.latencies,
+      timerResolutionMs: timerResolution(),
+    }
+  },
+}
+
+window.__scenario = {
+  async run({ id, op, ...params }) {
+    let result
+    try {
+      result = await OPS[op](params)
+    }
+    catch (err) {
+      result = { error: String(err) }
+    }
+    await invoke('scenario_reply', { id, result })
+  },
+}
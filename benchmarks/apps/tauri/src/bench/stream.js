@@ -0,0 +1,90 @@
+/**
+ * Ordered streaming driver (BENCHMARK=stream)
+ *
+ * Receives the same chunk stream twice — first through a tauri::ipc::Channel,
+ * then as `bench:chunk` events — recording every chunk's sequence number and
+ * arrival time. Both runs go back to Rust via `stream_report`.
+ *
+ * The channel is wired up by hand the way @tauri-apps/api's Channel does it:
+ * a callback registered with transformCallback, passed as `__CHANNEL__:<id>`.
+ * Rust calls it with `{ message, index }` per chunk and `{ end, index }` once
+ * the channel is dropped. Large chunks are fetched asynchronously, so these
+ * can arrive out of order; like the API's Channel, the driver holds early
+ * ones back and delivers everything by index, so a chunk's arrival time is
+ * when an app would see it. `heldBack` counts the chunks that had to wait.
+ */
+import { internals, invoke, listen } from './util.js'
+
+function recorder(transport) {
+  const run = { transport, sent: 0, startedAt: 0, seqs: [], arrivals: [], heldBack: 0 }
+  let expected = null
+  let finished
+  const done = new Promise((resolve) => {
+    finished = resolve
+  })
+  const check = () => {
+    if (expected !== null && run.seqs.length >= expected) finished(run)
+  }
+  return {
+    run,
+    done,
+    chunk(seq) {
+      run.seqs.push(seq)
+      run.arrivals.push(performance.now())
+      check()
+    },
+    end(sent) {
+      run.sent = sent
+      expected = sent
+      check()
+    },
+  }
+}
+
+async function viaChannel() {
+  const rec = recorder('channel')
+  const deliver = (msg) => {
+    if (msg.end) rec.end(msg.index)
+    else rec.chunk(msg.message.seq)
+  }
+  const pending = new Map()
+  let next = 0
+  const id = internals.transformCallback((msg) => {
+    if (msg.index !== next) {
+      if (!msg.end) rec.run.heldBack++
+      pending.set(msg.index, msg)
+      return
+    }
+    deliver(msg)
+    next++
+    while (pending.has(next)) {
+      deliver(pending.get(next))
+      pending.delete(next)
+      next++
+    }
+  })
+
+  rec.run.startedAt = performance.now()
+  await invoke('stream_channel', { channel: `__CHANNEL__:${id}` })
+  return rec.done
+}
+
+async function viaEmit() {
+  const rec = recorder('emit')
+  await listen('bench:chunk', ({ payload }) => rec.chunk(payload.seq))
+  await listen('bench:chunk-end', ({ payload }) => rec.end(payload.sent))
+
+  rec.run.startedAt = performance.now()
+  await invoke('stream_emit')
+  return rec.done
+}
+
+async function main() {
+  const results = [await viaChannel(), await viaEmit()]
+  await invoke('stream_report', { results })
+}
+
+main().catch(err => {
+  console.error('stream benchmark failed:', err)
+  invoke('bench_fail', { message: `stream benchmark failed: ${err}` })
+})
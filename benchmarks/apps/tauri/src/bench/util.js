@@ -0,0 +1,37 @@
+/**
+ * Helpers shared by the driver pages.
+ *
+ * Everything goes through __TAURI_INTERNALS__ directly so the pages don't
+ * need `withGlobalTauri` (which would inject the full API into every window)
+ * or a JS bundle.
+ */
+export const internals = window.__TAURI_INTERNALS__
+export const invoke = (cmd, args) => internals.invoke(cmd, args)
+
+/**
+ * Listen for `event` through the core event plugin — the same call
+ * @tauri-apps/api's listen() makes. Resolves to the listener id.
+ */
+export function listen(event, handler) {
+  return invoke('plugin:event|listen', {
+    event,
+    target: { kind: 'Any' },
+    handler: internals.transformCallback(handler),
+  })
+}
+
+/**
+ * The smallest step performance.now() takes here; engines coarsen it. Sent
+ * with samples timed in the page so Rust can size its histogram buckets to
+ * it (see histogram.rs).
+ */
+export function timerResolution() {
+  let step = Infinity
+  for (let i = 0; i < 10; i++) {
+    const start = performance.now()
+    let now = start
+    while (now === start) now = performance.now()
+    step = Math.min(step, now - start)
+  }
+  return step
+}
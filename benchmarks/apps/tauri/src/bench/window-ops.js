@@ -0,0 +1,73 @@
+/**
+ * Window-operation latency driver (BENCHMARK=window-ops)
+ *
+ * Opens the "target" window and runs every operation `windowOpIterations`
+ * times against it. Each run is timed from invoke until the command returns,
+ * which it does only once the change is visible on the window; the command's
+ * own operation-to-effect time is kept alongside. Where an operation needs
+ * the window in a particular state first (hidden before show, restored before
+ * minimize, open before close) that is arranged untimed.
+ *
+ * Sizes and positions alternate between two values so every call changes
+ * something. An operation whose first three attempts all fail is abandoned.
+ * Samples go back to Rust via `window_ops_report`.
+ */
+import { invoke } from './util.js'
+
+const alt = (i, a, b) => (i % 2 === 0 ? b : a)
+
+const OPS = [
+  { op: 'show', prepare: () => invoke('window_hide'), run: () => invoke('window_show') },
+  { op: 'hide', prepare: () => invoke('window_show'), run: () => invoke('window_hide') },
+  { op: 'toggle', run: () => invoke('window_toggle') },
+  { op: 'minimize', prepare: () => invoke('window_unminimize'), run: () => invoke('window_minimize') },
+  { op: 'set_title', run: i => invoke('window_set_title', { title: `Hello World ${i}` }) },
+  {
+    op: 'set_size',
+    run: i => invoke('window_set_size', { width: alt(i, 400, 420), height: alt(i, 300, 320) }),
+  },
+  {
+    op: 'set_position',
+    run: i => invoke('window_set_position', { x: alt(i, 100, 140), y: alt(i, 100, 140) }),
+  },
+  { op: 'close', prepare: () => invoke('window_open'), run: () => invoke('window_close') },
+]
+
+async function main() {
+  const { windowOpIterations } = await invoke('bench_config')
+  await invoke('window_open')
+
+  const results = []
+  for (const { op, prepare, run } of OPS) {
+    const samples = []
+    const effects = []
+    let failures = 0
+    for (let i = 0; i < windowOpIterations; i++) {
+      try {
+        if (prepare) await prepare()
+        const start = performance.now()
+        const effect = await run(i)
+        samples.push(performance.now() - start)
+        effects.push(effect)
+      }
+      catch (err) {
+        failures++
+        console.error(`${op} failed:`, err)
+        // Each failure costs the full effect timeout; one that keeps failing
+        // (minimize without a window manager) isn't worth waiting out.
+        if (failures >= 3 && samples.length === 0) break
+      }
+    }
+    results.push({ op, samples, effects, failures })
+    await invoke('window_open')
+    await invoke('window_show')
+    await invoke('window_unminimize').catch(() => {})
+  }
+
+  await invoke('window_ops_report', { results })
+}
+
+main().catch(err => {
+  console.error('window-ops benchmark failed:', err)
+  invoke('bench_fail', { message: `window-ops benchmark failed: ${err}` })
+})
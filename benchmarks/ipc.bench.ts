@@ -26,9 +26,16 @@
  *
  * This is a fair comparison: same serialization mechanism (JSON.stringify/parse),
  * measuring the actual protocol overhead of each framework's message format.
+ *
+ * If the Tauri app is built, a second section spawns it with BENCHMARK=ipc and
+ * reports the real webview -> Rust -> webview invoke latency it measures
+ * in-process (see apps/tauri/src/bench/ipc.js), including the p99.9 from the
+ * app's latency histogram, then runs BENCHMARK=payload to
+ * show how the same path scales from 1 KB to 10 MB with JSON strings, JSON
+ * byte arrays and raw binary bodies (apps/tauri/src/bench/payload.js).
  */
 import { bench, boxplot, run, summary } from 'mitata'
-import { header } from './utils'
+import { findTauriBinary, formatBytes, header, measureProcess, tauriDeadlines } from './utils'
 
 header('IPC Protocol Overhead')
 
@@ -199,3 +206,103 @@ boxplot(() => {
 })
 
 await run()
+
+// ---------------------------------------------------------------------------
+// Real round trips (Tauri)
+// ---------------------------------------------------------------------------
+interface IpcLatency {
+  count: number
+  min: number
+  mean: number
+  p50: number
+  p90: number
+  p99: number
+  max: number
+}
+
+/** The app's HDR-style histogram of the same samples (see histogram.rs). */
+interface IpcHistogram {
+  count: number
+  p50: number
+  p90: number
+  p99: number
+  p99_9: number
+  max: number
+  /** Step of the webview's performance.now(); no bucket is narrower. */
+  resolution_ms: number
+  buckets: Array<[number, number]>
+}
+
+const tauriBin = findTauriBinary()
+if (tauriBin) {
+  header('Tauri IPC Round Trip (webview -> Rust -> webview)')
+
+  const { exitCode, stdout } = await measureProcess([tauriBin], {
+    env: { BENCHMARK: 'ipc', ...tauriDeadlines(60_000) },
+    timeoutMs: 60_000,
+  })
+  const line = stdout.split('\n').find(l => l.startsWith('{'))
+
+  if (exitCode !== 0 || !line) {
+    console.log(`  Tauri IPC run failed (exit code ${exitCode})\n`)
+  }
+  else {
+    const report = JSON.parse(line) as {
+      iterations: number
+      commands: Array<{
+        command: string
+        latency_ms: IpcLatency | null
+        latency_histogram?: IpcHistogram | null
+      }>
+    }
+    console.log(`  Iterations per command: ${report.iterations}`)
+    const resolution = report.commands.find(c => c.latency_histogram)?.latency_histogram?.resolution_ms
+    if (resolution !== undefined) console.log(`  Webview timer resolution: ${resolution.toFixed(3)} ms`)
+    console.log()
+    for (const c of report.commands) {
+      if (!c.latency_ms) continue
+      const l = c.latency_ms
+      const tail = c.latency_histogram ? `   p99.9 ${c.latency_histogram.p99_9.toFixed(3)} ms` : ''
+      console.log(
+        `  ${c.command.padEnd(14)} p50 ${l.p50.toFixed(3)} ms   p90 ${l.p90.toFixed(3)} ms   p99 ${l.p99.toFixed(3)} ms${tail}   max ${l.max.toFixed(3)} ms`,
+      )
+    }
+    console.log()
+  }
+}
+
+// ---------------------------------------------------------------------------
+// Payload scaling (Tauri)
+// ---------------------------------------------------------------------------
+if (tauriBin) {
+  header('Tauri IPC Payload Scaling (p50 latency / throughput)')
+
+  const { exitCode, stdout } = await measureProcess([tauriBin], {
+    env: { BENCHMARK: 'payload', ...tauriDeadlines(300_000) },
+    timeoutMs: 300_000,
+  })
+  const line = stdout.split('\n').find(l => l.startsWith('{'))
+
+  if (exitCode !== 0 || !line) {
+    console.log(`  Tauri payload run failed (exit code ${exitCode})\n`)
+  }
+  else {
+    const report = JSON.parse(line) as {
+      iterations: number
+      results: Array<{ form: string; bytes: number; latency_ms: IpcLatency | null; throughput_mb_s: number | null }>
+    }
+    const forms = [...new Set(report.results.map(r => r.form))]
+    const sizes = [...new Set(report.results.map(r => r.bytes))]
+    console.log(`  Iterations per size: ${report.iterations}\n`)
+    console.log(`  ${'Size'.padEnd(10)}${forms.map(f => f.padStart(26)).join('')}`)
+    for (const size of sizes) {
+      const cells = forms.map((form) => {
+        const r = report.results.find(x => x.form === form && x.bytes === size)
+        if (!r?.latency_ms) return 'n/a'.padStart(26)
+        return `${r.latency_ms.p50.toFixed(2)} ms / ${(r.throughput_mb_s ?? 0).toFixed(1)} MB/s`.padStart(26)
+      })
+      console.log(`  ${formatBytes(size).padEnd(10)}${cells.join('')}`)
+    }
+    console.log()
+  }
+}
@@ -13,6 +13,11 @@
  *   5. Repeat 3 times and report median
  *
  * This is an objective measurement — no synthetic simulations.
+ *
+ * On Linux the Tauri app can also account for its own process tree with
+ * BENCHMARK=memory, reading smaps_rollup for itself and its WebKit children.
+ * Summed RSS counts shared WebKit libraries once per process; PSS splits
+ * shared pages between them, so the PSS total is reported alongside.
  */
 import { join } from 'node:path'
 import {
@@ -24,7 +29,9 @@ import {
   formatBytes,
   getProcessRSS,
   header,
+  measureProcess,
   reportTable,
+  tauriDeadlines,
 } from './utils'
 
 const APPS = join(import.meta.dir, 'apps')
@@ -206,3 +213,39 @@ if (results.length >= 2) {
 }
 
 console.log()
+
+// ---------------------------------------------------------------------------
+// Proportional set size (Tauri, Linux)
+// ---------------------------------------------------------------------------
+interface TreeMemory {
+  processes: Array<{ pid: number; name: string; rss_kb: number | null; pss_kb: number | null; uss_kb: number | null }>
+  total: { rss_kb: number; pss_kb: number; uss_kb: number }
+}
+
+if (tauriBin && process.platform === 'linux') {
+  const trees: TreeMemory[] = []
+  for (let i = 0; i < SAMPLES; i++) {
+    const { exitCode, stdout } = await measureProcess([tauriBin], {
+      env: {
+        BENCHMARK: 'memory',
+        BENCHMARK_SETTLE_MS: String(STABILIZE_MS),
+        ...tauriDeadlines(STABILIZE_MS + 15_000),
+      },
+      timeoutMs: STABILIZE_MS + 15_000,
+    })
+    const line = stdout.split('\n').find(l => l.startsWith('{'))
+    if (exitCode === 0 && line) trees.push(JSON.parse(line) as TreeMemory)
+  }
+
+  if (trees.length > 0) {
+    console.log('Tauri process tree (in-process smaps_rollup, median):')
+    const med = (key: 'rss_kb' | 'pss_kb' | 'uss_kb') => median(trees.map(t => t.total[key])) * 1024
+    reportTable([
+      { label: 'RSS', value: formatBytes(med('rss_kb')), note: 'sum across processes' },
+      { label: 'PSS', value: formatBytes(med('pss_kb')), note: 'shared pages split fairly' },
+      { label: 'USS', value: formatBytes(med('uss_kb')), note: 'private pages only' },
+    ])
+    console.log(`  Processes: ${trees[0].processes.map(p => p.name).join(', ')}`)
+    console.log()
+  }
+}
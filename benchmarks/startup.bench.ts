@@ -11,9 +11,20 @@
  *
  *   - Craft:        --benchmark flag: creates window, prints "ready", exits immediately
  *   - Electron:     BENCHMARK=1 env: quits after `did-finish-load` (HTML fully parsed)
- *   - Tauri:        BENCHMARK=1 env: quits ~50ms after setup() (window created)
+ *   - Tauri:        BENCHMARK=1 env: quits on PageLoadEvent::Finished (HTML loaded)
+ *                   (BENCHMARK_READY=timer restores the old ~50ms after setup())
+ *   - Tauri (paint): BENCHMARK_READY=paint: quits once "Hello World" is painted
  *   - Electrobun:   BENCHMARK=1 env: quits ~50ms after window creation
  *   - React Native: BENCHMARK=1 env: quits ~100ms after applicationDidFinishLaunching
+ *
+ *   Tauri is additionally run with BENCHMARK_REPORT=json, which timestamps each
+ *   startup phase in-process, plus RunEvent::ExitRequested/Exit for teardown;
+ *   the p50 of every phase is shown as a breakdown.
+ *
+ *   TAURI_ASSET_SWEEP=0,100,1000 additionally rebuilds the Tauri app once per
+ *   count with BENCHMARK_SYNTHETIC_ASSETS (build.rs generates a page
+ *   referencing that many JS/CSS/SVG files) and reports BENCHMARK=synthetic
+ *   startup and page-load times for each, next to Craft's inline HTML string.
  */
 import { join } from 'node:path'
 import {
@@ -23,6 +34,9 @@ import {
   findRNMacOSBinary,
   findTauriBinary,
   header,
+  measureProcess,
+  tauriDeadlines,
+  tauriError,
 } from './utils'
 
 const APPS = join(import.meta.dir, 'apps')
@@ -131,6 +145,94 @@ async function measureWithKill(
   }
 }
 
+/** Say why a Tauri run failed, from the error record it left on stderr. */
+function warnTauriFailure(exitCode: number | null, stderr: string) {
+  if (exitCode === 0) return
+  const error = tauriError(stderr)
+  if (error)
+    console.warn(`  Tauri run failed (${error.kind}, exit ${error.exit_code}): ${error.message}`)
+  else
+    console.warn(`  Tauri run failed (exit ${exitCode ?? 'killed'})`)
+}
+
+/** Phase timestamps from the Tauri app's BENCHMARK_REPORT=json output. */
+type TauriPhases = Record<string, number | null>
+
+/**
+ * Run the Tauri app with BENCHMARK_REPORT=json and collect each run's
+ * phase timestamps, rebased so the earliest recorded phase is 0.
+ */
+async function measureTauriPhases(bin: string): Promise<TauriPhases[]> {
+  const runs: TauriPhases[] = []
+
+  for (let i = 0; i < ITERATIONS; i++) {
+    const { exitCode, stdout, stderr } = await measureProcess([bin], {
+      env: { BENCHMARK: '1', BENCHMARK_REPORT: 'json', ...tauriDeadlines(15_000) },
+      timeoutMs: 15_000,
+    })
+    warnTauriFailure(exitCode, stderr)
+    const line = stdout.split('\n').find(l => l.startsWith('{'))
+    if (exitCode !== 0 || !line) continue
+
+    const phases = JSON.parse(line).phases as TauriPhases
+    const origin = Math.min(...Object.values(phases).filter((v): v is number => v !== null))
+    runs.push(Object.fromEntries(
+      Object.entries(phases).map(([k, v]) => [k, v === null ? null : v - origin]),
+    ))
+  }
+
+  return runs
+}
+
+/** Startup and page-load times for one synthetic frontend size. */
+interface AssetScalingRow {
+  assets: number
+  startup: number[]
+  pageLoad: number[]
+}
+
+/**
+ * Rebuild the Tauri app with each synthetic asset count and time
+ * BENCHMARK=synthetic runs of it. The app is rebuilt without synthetic assets
+ * afterwards so the other benchmarks see the normal frontend.
+ */
+async function measureTauriAssetScaling(counts: number[]): Promise<AssetScalingRow[]> {
+  const cwd = join(APPS, 'tauri/src-tauri')
+  const build = (assets: number) => Bun.spawnSync({
+    cmd: ['cargo', 'build', '--release'],
+    cwd,
+    env: { ...process.env, BENCHMARK_SYNTHETIC_ASSETS: String(assets) },
+    stdout: 'ignore',
+    stderr: 'inherit',
+  }).exitCode === 0
+
+  const rows: AssetScalingRow[] = []
+  for (const assets of counts) {
+    console.log(`  Building Tauri with ${assets} synthetic assets...`)
+    if (!build(assets)) continue
+
+    const bin = join(cwd, 'target/release/tauri-hello-world')
+    const row: AssetScalingRow = { assets, startup: [], pageLoad: [] }
+    for (let i = 0; i < ITERATIONS; i++) {
+      const { exitCode, stdout, stderr } = await measureProcess([bin], {
+        env: { BENCHMARK: 'synthetic', ...tauriDeadlines(30_000) },
+        timeoutMs: 30_000,
+      })
+      warnTauriFailure(exitCode, stderr)
+      const line = stdout.split('\n').find(l => l.startsWith('{'))
+      if (exitCode !== 0 || !line) continue
+
+      const result = JSON.parse(line)
+      if (result.startup_ms !== null) row.startup.push(result.startup_ms)
+      if (result.page_load_ms !== null) row.pageLoad.push(result.page_load_ms)
+    }
+    rows.push(row)
+  }
+
+  build(0)
+  return rows
+}
+
 function stats(times: number[]) {
   const sorted = [...times].sort((a, b) => a - b)
   const avg = times.reduce((a, b) => a + b, 0) / times.length
@@ -151,6 +253,8 @@ function fmtMs(ms: number): string {
 // Run measurements
 // ---------------------------------------------------------------------------
 const results: TimingResult[] = []
+let tauriPhases: TauriPhases[] = []
+let tauriAssetScaling: AssetScalingRow[] = []
 
 // --- Craft ---
 const craftBin = findCraftBinary()
@@ -181,9 +285,23 @@ const tauriBin = findTauriBinary()
 if (tauriBin) {
   console.log('  Measuring Tauri...')
   const r = await measureAutoQuit('Tauri', [tauriBin], {
-    env: { BENCHMARK: '1' },
+    env: { BENCHMARK: '1', ...tauriDeadlines(15_000) },
   })
   results.push(r)
+
+  console.log('  Measuring Tauri (first paint)...')
+  const paint = await measureAutoQuit('Tauri (paint)', [tauriBin], {
+    env: { BENCHMARK: '1', BENCHMARK_READY: 'paint', ...tauriDeadlines(15_000) },
+  })
+  results.push(paint)
+
+  console.log('  Measuring Tauri startup phases...')
+  tauriPhases = await measureTauriPhases(tauriBin)
+
+  const sweep = process.env.TAURI_ASSET_SWEEP
+  if (sweep) {
+    tauriAssetScaling = await measureTauriAssetScaling(sweep.split(',').map(Number))
+  }
 }
 
 // --- Electrobun ---
@@ -246,3 +364,30 @@ if (results.length >= 2) {
 }
 
 console.log()
+
+// Per-phase breakdown (Tauri)
+if (tauriPhases.length > 0) {
+  console.log('Tauri startup phases (p50, ms since first recorded phase):')
+  for (const phase of Object.keys(tauriPhases[0])) {
+    const times = tauriPhases.map(p => p[phase]).filter((v): v is number => v !== null)
+    const value = times.length > 0 ? fmtMs(stats(times).p50) : 'n/a'
+    console.log(`  ${phase.padEnd(16)} ${value.padStart(10)}`)
+  }
+  console.log()
+}
+
+// Embedded-asset scaling (Tauri)
+if (tauriAssetScaling.length > 0) {
+  const craft = results.find(r => r.framework === 'Craft')
+  console.log('Tauri startup vs embedded asset count (p50, in-process):')
+  console.log('  assets      startup    page load')
+  for (const row of tauriAssetScaling) {
+    const startup = row.startup.length > 0 ? fmtMs(stats(row.startup).p50) : 'n/a'
+    const pageLoad = row.pageLoad.length > 0 ? fmtMs(stats(row.pageLoad).p50) : 'n/a'
+    console.log(`  ${String(row.assets).padStart(6)}  ${startup.padStart(11)}  ${pageLoad.padStart(11)}`)
+  }
+  if (craft) {
+    console.log(`  Craft (inline HTML string, wall clock): ${fmtMs(stats(craft.times).p50)}`)
+  }
+  console.log()
+}
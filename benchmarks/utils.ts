@@ -177,7 +177,7 @@ export async function measureProcess(
     env?: Record<string, string>
     timeoutMs?: number
   } = {},
-): Promise<{ timeMs: number; exitCode: number | null; stdout: string }> {
+): Promise<{ timeMs: number; exitCode: number | null; stdout: string; stderr: string }> {
   const timeoutMs = opts.timeoutMs ?? 10_000
   const start = performance.now()
 
@@ -196,8 +196,45 @@ export async function measureProcess(
 
   const timeMs = performance.now() - start
   const stdout = await new Response(proc.stdout).text()
+  const stderr = await new Response(proc.stderr).text()
 
-  return { timeMs, exitCode, stdout }
+  return { timeMs, exitCode, stdout, stderr }
+}
+
+/**
+ * Watchdog deadlines for a Tauri run the harness kills after `timeoutMs`
+ * (see watchdog.rs). The three phases together stay under the kill timer, so
+ * a hung run ends itself with exit code 9 and a partial report naming the
+ * phase, rather than being killed with nothing to show.
+ */
+export function tauriDeadlines(timeoutMs: number): Record<string, string> {
+  const budget = timeoutMs - Math.min(2_000, timeoutMs / 10)
+  const phase = Math.min(10_000, Math.floor(budget / 3))
+  return {
+    BENCHMARK_DEADLINE_WINDOW_MS: String(phase),
+    BENCHMARK_DEADLINE_PAGE_LOAD_MS: String(phase),
+    BENCHMARK_DEADLINE_SCENARIO_MS: String(Math.floor(budget - 2 * phase)),
+  }
+}
+
+/** The error record a failed Tauri benchmark run writes to stderr. */
+export interface TauriError {
+  kind: string
+  exit_code: number
+  message: string
+}
+
+/** Find the Tauri app's error record in `stderr`, if it wrote one. */
+export function tauriError(stderr: string): TauriError | undefined {
+  for (const line of stderr.split('\n')) {
+    if (!line.startsWith('{')) continue
+    try {
+      const record = JSON.parse(line)
+      if (record.event === 'error') return record
+    }
+    catch {}
+  }
+  return undefined
 }
 
 // ---------------------------------------------------------------------------